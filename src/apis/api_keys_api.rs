@@ -0,0 +1,269 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use super::{configuration, ContentType, Error, FromErrorResponse, Response};
+use crate::{apis::ResponseContent, models};
+use reqwest;
+use serde::{de::Error as _, Deserialize, Serialize};
+
+/// struct for typed errors of method [`create_api_key`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CreateApiKeyError {
+    Status400(models::ErrorResponse),
+    Status401(models::ErrorResponse),
+    Status403(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
+impl FromErrorResponse for CreateApiKeyError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            400 => Some(Self::Status400(body)),
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
+/// struct for typed errors of method [`delete_api_key`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeleteApiKeyError {
+    Status401(models::ErrorResponse),
+    Status403(models::ErrorResponse),
+    Status404(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
+impl FromErrorResponse for DeleteApiKeyError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            404 => Some(Self::Status404(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
+/// struct for typed errors of method [`list_api_keys`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListApiKeysError {
+    Status401(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
+impl FromErrorResponse for ListApiKeysError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
+/// Creates a new API key for the authenticated user. The full key is only returned once.
+pub async fn create_api_key(
+    configuration: &configuration::Configuration,
+    create_api_key_request: Option<models::CreateApiKeyRequest>,
+) -> Result<models::CreateApiKeyResponse, Error<CreateApiKeyError>> {
+    create_api_key_with_response(configuration, create_api_key_request)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`create_api_key`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn create_api_key_with_response(
+    configuration: &configuration::Configuration,
+    create_api_key_request: Option<models::CreateApiKeyRequest>,
+) -> Result<Response<models::CreateApiKeyResponse>, Error<CreateApiKeyError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_body_create_api_key_request = create_api_key_request;
+
+    let uri_str = format!("{}/api/v1/settings/api-keys", configuration.base_path);
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    req_builder = req_builder.json(&p_body_create_api_key_request);
+
+    let req = req_builder.build()?;
+    let resp = super::retry::execute(configuration, req).await?;
+
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::CreateApiKeyResponse`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::CreateApiKeyResponse`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity = CreateApiKeyError::from_response(status, &content);
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            headers,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Deletes an API key
+pub async fn delete_api_key(
+    configuration: &configuration::Configuration,
+    id: &str,
+) -> Result<models::DeleteApiKeyResponse, Error<DeleteApiKeyError>> {
+    delete_api_key_with_response(configuration, id)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`delete_api_key`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn delete_api_key_with_response(
+    configuration: &configuration::Configuration,
+    id: &str,
+) -> Result<Response<models::DeleteApiKeyResponse>, Error<DeleteApiKeyError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_id = id;
+
+    let uri_str = format!(
+        "{}/api/v1/settings/api-keys/{id}",
+        configuration.base_path,
+        id = crate::apis::urlencode(p_path_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::DELETE, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+
+    let req = req_builder.build()?;
+    let resp = super::retry::execute(configuration, req).await?;
+
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::DeleteApiKeyResponse`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::DeleteApiKeyResponse`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity = DeleteApiKeyError::from_response(status, &content);
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            headers,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Returns all API keys for the authenticated user
+pub async fn list_api_keys(
+    configuration: &configuration::Configuration,
+) -> Result<models::ApiKeyListResponse, Error<ListApiKeysError>> {
+    list_api_keys_with_response(configuration)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`list_api_keys`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn list_api_keys_with_response(
+    configuration: &configuration::Configuration,
+) -> Result<Response<models::ApiKeyListResponse>, Error<ListApiKeysError>> {
+    let uri_str = format!("{}/api/v1/settings/api-keys", configuration.base_path);
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+
+    let req = req_builder.build()?;
+    let resp = super::retry::execute(configuration, req).await?;
+
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::ApiKeyListResponse`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::ApiKeyListResponse`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity = ListApiKeysError::from_response(status, &content);
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            headers,
+            content,
+            entity,
+        }))
+    }
+}
@@ -0,0 +1,144 @@
+//! Request authentication.
+//!
+//! Every authenticated request in [`crate::apis`] gets its `Authorization`
+//! header right before each attempt is sent. The first credential set on the
+//! [`Configuration`] wins, in this order:
+//!
+//! 1. [`credential_provider`](super::configuration::Configuration::credential_provider),
+//!    asked for a bearer token per attempt
+//! 2. [`bearer_access_token`](super::configuration::Configuration::bearer_access_token)
+//! 3. [`api_key`](super::configuration::Configuration::api_key), sent as
+//!    `Authorization: <prefix> <key>` with the prefix defaulting to `Bearer`
+//! 4. [`oauth_access_token`](super::configuration::Configuration::oauth_access_token)
+//! 5. [`basic_auth`](super::configuration::Configuration::basic_auth)
+//!
+//! A request is never sent without credentials: if none is set, it fails with
+//! [`AuthError::MissingCredentials`].
+
+use std::error;
+use std::fmt;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+
+use super::configuration::{ApiKey, Configuration, Secret};
+
+/// Scheme used for an [`ApiKey`] without a prefix.
+const DEFAULT_API_KEY_PREFIX: &str = "Bearer";
+
+/// Error of authenticating a request before it is sent.
+#[derive(Debug)]
+pub enum AuthError {
+    /// No credential field of the configuration is set.
+    MissingCredentials,
+    /// A credential contains characters not allowed in an HTTP header.
+    InvalidHeader,
+    /// The [`CredentialProvider`](super::credentials::CredentialProvider)
+    /// could not supply a token.
+    Provider(Box<dyn error::Error + Send + Sync>),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingCredentials => write!(
+                f,
+                "no credentials configured; set credential_provider, bearer_access_token, api_key, oauth_access_token or basic_auth"
+            ),
+            AuthError::InvalidHeader => {
+                write!(f, "credential is not a valid Authorization header value")
+            }
+            AuthError::Provider(e) => write!(f, "credential provider failed: {e}"),
+        }
+    }
+}
+
+impl error::Error for AuthError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            AuthError::Provider(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// The credential a request is authenticated with.
+#[derive(Debug, Clone, Copy)]
+pub enum Credential<'a> {
+    Bearer(&'a Secret),
+    ApiKey(&'a ApiKey),
+    OAuth(&'a Secret),
+    Basic {
+        username: &'a str,
+        password: Option<&'a Secret>,
+    },
+}
+
+impl<'a> Credential<'a> {
+    /// The static credential used for requests made with `configuration`.
+    /// Does not consult the
+    /// [`credential_provider`](super::configuration::Configuration::credential_provider).
+    pub fn from_configuration(configuration: &'a Configuration) -> Result<Self, AuthError> {
+        if let Some(ref token) = configuration.bearer_access_token {
+            return Ok(Credential::Bearer(token));
+        }
+        if let Some(ref api_key) = configuration.api_key {
+            return Ok(Credential::ApiKey(api_key));
+        }
+        if let Some(ref token) = configuration.oauth_access_token {
+            return Ok(Credential::OAuth(token));
+        }
+        if let Some((ref username, ref password)) = configuration.basic_auth {
+            return Ok(Credential::Basic {
+                username,
+                password: password.as_ref(),
+            });
+        }
+        Err(AuthError::MissingCredentials)
+    }
+
+    /// Value of the `Authorization` header, marked sensitive.
+    pub fn header_value(&self) -> Result<HeaderValue, AuthError> {
+        let value = match self {
+            Credential::Bearer(token) | Credential::OAuth(token) => {
+                format!("Bearer {}", token.expose_secret())
+            }
+            Credential::ApiKey(api_key) => format!(
+                "{} {}",
+                api_key.prefix.as_deref().unwrap_or(DEFAULT_API_KEY_PREFIX),
+                api_key.key.expose_secret()
+            ),
+            Credential::Basic { username, password } => {
+                let plain = match password {
+                    Some(password) => format!("{username}:{}", password.expose_secret()),
+                    None => format!("{username}:"),
+                };
+                format!("Basic {}", STANDARD.encode(plain))
+            }
+        };
+        let mut header = HeaderValue::try_from(value).map_err(|_| AuthError::InvalidHeader)?;
+        header.set_sensitive(true);
+        Ok(header)
+    }
+}
+
+/// Sets the `Authorization` header of `request` from `configuration`.
+/// Returns the token if it came from the credential provider.
+pub(crate) async fn authenticate(
+    configuration: &Configuration,
+    request: &mut reqwest::Request,
+) -> Result<Option<Secret>, AuthError> {
+    let (header, provided) = match configuration.credential_provider {
+        Some(ref provider) => {
+            let token = provider.token().await?;
+            (Credential::Bearer(&token).header_value()?, Some(token))
+        }
+        None => (
+            Credential::from_configuration(configuration)?.header_value()?,
+            None,
+        ),
+    };
+    request.headers_mut().insert(AUTHORIZATION, header);
+    Ok(provided)
+}
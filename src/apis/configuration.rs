@@ -8,23 +8,36 @@
  * Generated by: https://openapi-generator.tech
  */
 
+use std::sync::Arc;
+
+pub use super::credentials::CredentialProvider;
+pub use super::rate_limit::{Plan, RateLimiter};
+pub use super::retry::RetryPolicy;
+pub use super::secret::Secret;
+
 #[derive(Debug, Clone)]
 pub struct Configuration {
     pub base_path: String,
     pub user_agent: Option<String>,
     pub client: reqwest::Client,
     pub basic_auth: Option<BasicAuth>,
-    pub oauth_access_token: Option<String>,
-    pub bearer_access_token: Option<String>,
+    pub oauth_access_token: Option<Secret>,
+    pub bearer_access_token: Option<Secret>,
     pub api_key: Option<ApiKey>,
+    /// Asked for a bearer token per request; takes precedence over the other
+    /// credential fields.
+    pub credential_provider: Option<Arc<dyn CredentialProvider>>,
+    pub retry_policy: RetryPolicy,
+    /// Shared client-side limiter; `None` sends requests without pacing.
+    pub rate_limiter: Option<Arc<RateLimiter>>,
 }
 
-pub type BasicAuth = (String, Option<String>);
+pub type BasicAuth = (String, Option<Secret>);
 
 #[derive(Debug, Clone)]
 pub struct ApiKey {
     pub prefix: Option<String>,
-    pub key: String,
+    pub key: Secret,
 }
 
 impl Configuration {
@@ -43,6 +56,9 @@ impl Default for Configuration {
             oauth_access_token: None,
             bearer_access_token: None,
             api_key: None,
+            credential_provider: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
         }
     }
 }
@@ -0,0 +1,167 @@
+//! Credentials fetched per request.
+//!
+//! A [`CredentialProvider`] set on
+//! [`Configuration::credential_provider`](super::configuration::Configuration::credential_provider)
+//! is asked for a bearer token before every attempt, so rotated keys are
+//! picked up without rebuilding the configuration. When the API answers
+//! `401 Unauthorized`, the provider is told which token was rejected and may
+//! refresh it; the request is then sent once more with the new token.
+
+use std::error;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use futures_util::future::BoxFuture;
+
+use super::auth::AuthError;
+use super::configuration::Secret;
+
+/// Source of the bearer token sent with each request.
+pub trait CredentialProvider: fmt::Debug + Send + Sync {
+    /// Token for the next request.
+    fn token(&self) -> BoxFuture<'_, Result<Secret, AuthError>>;
+
+    /// Called after the API rejected `rejected` with `401 Unauthorized`.
+    /// Returns whether a different token is now available, in which case the
+    /// request is retried once. The default never retries.
+    fn refresh<'a>(&'a self, rejected: &'a Secret) -> BoxFuture<'a, Result<bool, AuthError>> {
+        let _ = rejected;
+        Box::pin(std::future::ready(Ok(false)))
+    }
+}
+
+/// Lets a caller keep a handle to a provider it also hands to a configuration.
+impl<P: CredentialProvider + ?Sized> CredentialProvider for Arc<P> {
+    fn token(&self) -> BoxFuture<'_, Result<Secret, AuthError>> {
+        (**self).token()
+    }
+
+    fn refresh<'a>(&'a self, rejected: &'a Secret) -> BoxFuture<'a, Result<bool, AuthError>> {
+        (**self).refresh(rejected)
+    }
+}
+
+/// A fixed token.
+#[derive(Debug, Clone)]
+pub struct StaticToken(pub Secret);
+
+impl StaticToken {
+    pub fn new(token: impl Into<Secret>) -> StaticToken {
+        StaticToken(token.into())
+    }
+}
+
+impl CredentialProvider for StaticToken {
+    fn token(&self) -> BoxFuture<'_, Result<Secret, AuthError>> {
+        Box::pin(std::future::ready(Ok(self.0.clone())))
+    }
+}
+
+/// A token read from an environment variable on every request.
+#[derive(Debug, Clone)]
+pub struct EnvToken {
+    pub var: String,
+}
+
+impl EnvToken {
+    pub fn new(var: impl Into<String>) -> EnvToken {
+        EnvToken { var: var.into() }
+    }
+
+    fn read(&self) -> Result<Secret, AuthError> {
+        match std::env::var(&self.var) {
+            Ok(value) if !value.trim().is_empty() => Ok(value.trim().into()),
+            _ => Err(provider_error(format!(
+                "environment variable {} is not set",
+                self.var
+            ))),
+        }
+    }
+}
+
+impl Default for EnvToken {
+    /// Reads `IMGSRC_API_KEY`.
+    fn default() -> Self {
+        EnvToken::new("IMGSRC_API_KEY")
+    }
+}
+
+impl CredentialProvider for EnvToken {
+    fn token(&self) -> BoxFuture<'_, Result<Secret, AuthError>> {
+        Box::pin(std::future::ready(self.read()))
+    }
+
+    fn refresh<'a>(&'a self, rejected: &'a Secret) -> BoxFuture<'a, Result<bool, AuthError>> {
+        Box::pin(std::future::ready(
+            self.read().map(|token| token != *rejected),
+        ))
+    }
+}
+
+/// A token read from a file, e.g. one mounted by a secrets manager.
+///
+/// The file is re-read whenever its modification time changes, and after the
+/// API rejects the cached token. Surrounding whitespace is ignored.
+#[derive(Debug)]
+pub struct FileToken {
+    pub path: PathBuf,
+    cached: Mutex<Option<(SystemTime, Secret)>>,
+}
+
+impl FileToken {
+    pub fn new(path: impl Into<PathBuf>) -> FileToken {
+        FileToken {
+            path: path.into(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    async fn read(&self) -> Result<Secret, AuthError> {
+        let modified = tokio::fs::metadata(&self.path)
+            .await
+            .and_then(|meta| meta.modified())
+            .map_err(provider_error)?;
+        if let Some((at, ref token)) = *self.lock() {
+            if at == modified {
+                return Ok(token.clone());
+            }
+        }
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(provider_error)?;
+        let token = contents.trim();
+        if token.is_empty() {
+            return Err(provider_error(format!(
+                "credential file {} is empty",
+                self.path.display()
+            )));
+        }
+        let token = Secret::from(token);
+        *self.lock() = Some((modified, token.clone()));
+        Ok(token)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<(SystemTime, Secret)>> {
+        self.cached.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CredentialProvider for FileToken {
+    fn token(&self) -> BoxFuture<'_, Result<Secret, AuthError>> {
+        Box::pin(self.read())
+    }
+
+    fn refresh<'a>(&'a self, rejected: &'a Secret) -> BoxFuture<'a, Result<bool, AuthError>> {
+        Box::pin(async move {
+            // The file may have been replaced within the mtime resolution.
+            self.lock().take();
+            Ok(self.read().await? != *rejected)
+        })
+    }
+}
+
+fn provider_error(e: impl Into<Box<dyn error::Error + Send + Sync>>) -> AuthError {
+    AuthError::Provider(e.into())
+}
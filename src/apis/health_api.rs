@@ -0,0 +1,167 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use std::time::{Duration, Instant};
+
+use super::{configuration, ContentType, Error, FromErrorResponse, Response};
+use crate::{apis::ResponseContent, models};
+use reqwest;
+use serde::{de::Error as _, Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+/// struct for typed errors of method [`health_check`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum HealthCheckError {
+    UnknownValue(serde_json::Value),
+}
+
+impl FromErrorResponse for HealthCheckError {
+    fn from_status(_status: reqwest::StatusCode, _body: models::ErrorResponse) -> Option<Self> {
+        None
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
+/// Returns the current status of the API service. No authentication is sent.
+pub async fn health_check(
+    configuration: &configuration::Configuration,
+) -> Result<models::HealthResponse, Error<HealthCheckError>> {
+    health_check_with_response(configuration)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`health_check`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn health_check_with_response(
+    configuration: &configuration::Configuration,
+) -> Result<Response<models::HealthResponse>, Error<HealthCheckError>> {
+    let uri_str = format!("{}/health", configuration.base_path);
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+
+    let req = req_builder.build()?;
+    let resp = super::retry::execute_unauthenticated(configuration, req).await?;
+
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::HealthResponse`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::HealthResponse`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity = HealthCheckError::from_response(status, &content);
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            headers,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Readiness helper that caches the outcome of [`health_check`] for a short
+/// TTL, so frequent probes (e.g. a Kubernetes readiness probe) do not call the
+/// API every time.
+///
+/// Concurrent callers that find the cache expired share a single request.
+/// That request is sent once, bypassing the retry policy and rate limiter,
+/// and counts as not ready if it takes longer than the timeout.
+#[derive(Debug)]
+pub struct ReadinessCheck {
+    ttl: Duration,
+    timeout: Duration,
+    last: Mutex<Option<(Instant, bool)>>,
+}
+
+impl ReadinessCheck {
+    /// TTL used by [`ReadinessCheck::default`].
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(10);
+
+    /// Timeout of a single health check, unless set with
+    /// [`ReadinessCheck::with_timeout`].
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Creates a readiness check that reuses each result for `ttl`.
+    pub fn new(ttl: Duration) -> ReadinessCheck {
+        ReadinessCheck {
+            ttl,
+            timeout: ReadinessCheck::DEFAULT_TIMEOUT,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Sets how long a health check may take before it counts as not ready.
+    pub fn with_timeout(mut self, timeout: Duration) -> ReadinessCheck {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The time each result is reused for.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// How long a health check may take.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns whether img-src answered the health check successfully,
+    /// calling it only if the cached result is older than the TTL.
+    pub async fn is_ready(&self, configuration: &configuration::Configuration) -> bool {
+        let mut last = self.last.lock().await;
+        if let Some((checked_at, ready)) = *last {
+            if checked_at.elapsed() < self.ttl {
+                return ready;
+            }
+        }
+        let probe = configuration::Configuration {
+            retry_policy: configuration::RetryPolicy::disabled(),
+            rate_limiter: None,
+            ..configuration.clone()
+        };
+        let ready = tokio::time::timeout(self.timeout, health_check(&probe))
+            .await
+            .is_ok_and(|result| result.is_ok());
+        *last = Some((Instant::now(), ready));
+        ready
+    }
+
+    /// Discards the cached result so the next call checks again.
+    pub async fn invalidate(&self) {
+        *self.last.lock().await = None;
+    }
+}
+
+impl Default for ReadinessCheck {
+    fn default() -> Self {
+        ReadinessCheck::new(ReadinessCheck::DEFAULT_TTL)
+    }
+}
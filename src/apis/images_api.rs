@@ -8,11 +8,13 @@
  * Generated by: https://openapi-generator.tech
  */
 
-use super::{configuration, ContentType, Error};
+use super::{configuration, ContentType, Error, FromErrorResponse, Response};
 use crate::{apis::ResponseContent, models};
+use bytes::Bytes;
 use reqwest;
 use serde::{de::Error as _, Deserialize, Serialize};
 use tokio::fs::File as TokioFile;
+use tokio::io::AsyncRead;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
 /// struct for typed errors of method [`create_signed_url`]
@@ -27,6 +29,23 @@ pub enum CreateSignedUrlError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for CreateSignedUrlError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            404 => Some(Self::Status404(body)),
+            429 => Some(Self::Status429(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`delete_image`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -37,6 +56,21 @@ pub enum DeleteImageError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for DeleteImageError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            404 => Some(Self::Status404(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`delete_image_path`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -49,6 +83,23 @@ pub enum DeleteImagePathError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for DeleteImagePathError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            400 => Some(Self::Status400(body)),
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            404 => Some(Self::Status404(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`get_image`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -59,6 +110,21 @@ pub enum GetImageError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for GetImageError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            404 => Some(Self::Status404(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`list_images`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -68,6 +134,20 @@ pub enum ListImagesError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for ListImagesError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`search_images`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -78,6 +158,21 @@ pub enum SearchImagesError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for SearchImagesError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            400 => Some(Self::Status400(body)),
+            401 => Some(Self::Status401(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`upload_image`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -93,6 +188,26 @@ pub enum UploadImageError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for UploadImageError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            400 => Some(Self::Status400(body)),
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            404 => Some(Self::Status404(body)),
+            409 => Some(Self::Status409(body)),
+            413 => Some(Self::Status413(body)),
+            429 => Some(Self::Status429(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`update_visibility`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -104,12 +219,39 @@ pub enum UpdateVisibilityError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for UpdateVisibilityError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            404 => Some(Self::Status404(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// Create a time-limited signed URL for an image (Pro plan only)
 pub async fn create_signed_url(
     configuration: &configuration::Configuration,
     id: &str,
     create_signed_url_request: Option<models::CreateSignedUrlRequest>,
 ) -> Result<models::SignedUrlResponse, Error<CreateSignedUrlError>> {
+    create_signed_url_with_response(configuration, id, create_signed_url_request)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`create_signed_url`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn create_signed_url_with_response(
+    configuration: &configuration::Configuration,
+    id: &str,
+    create_signed_url_request: Option<models::CreateSignedUrlRequest>,
+) -> Result<Response<models::SignedUrlResponse>, Error<CreateSignedUrlError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_id = id;
     let p_body_create_signed_url_request = create_signed_url_request;
@@ -126,15 +268,13 @@ pub async fn create_signed_url(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
     req_builder = req_builder.json(&p_body_create_signed_url_request);
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -145,15 +285,18 @@ pub async fn create_signed_url(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::SignedUrlResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::SignedUrlResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<CreateSignedUrlError> = serde_json::from_str(&content).ok();
+        let entity = CreateSignedUrlError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -165,6 +308,16 @@ pub async fn delete_image(
     configuration: &configuration::Configuration,
     id: &str,
 ) -> Result<models::DeleteResponse, Error<DeleteImageError>> {
+    delete_image_with_response(configuration, id)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`delete_image`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn delete_image_with_response(
+    configuration: &configuration::Configuration,
+    id: &str,
+) -> Result<Response<models::DeleteResponse>, Error<DeleteImageError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_id = id;
 
@@ -180,14 +333,12 @@ pub async fn delete_image(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -198,15 +349,18 @@ pub async fn delete_image(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::DeleteResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::DeleteResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<DeleteImageError> = serde_json::from_str(&content).ok();
+        let entity = DeleteImageError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -219,6 +373,17 @@ pub async fn delete_image_path(
     username: &str,
     filepath: &str,
 ) -> Result<models::PathDeleteResponse, Error<DeleteImagePathError>> {
+    delete_image_path_with_response(configuration, username, filepath)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`delete_image_path`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn delete_image_path_with_response(
+    configuration: &configuration::Configuration,
+    username: &str,
+    filepath: &str,
+) -> Result<Response<models::PathDeleteResponse>, Error<DeleteImagePathError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_username = username;
     let p_path_filepath = filepath;
@@ -236,14 +401,12 @@ pub async fn delete_image_path(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -254,15 +417,18 @@ pub async fn delete_image_path(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::PathDeleteResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::PathDeleteResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<DeleteImagePathError> = serde_json::from_str(&content).ok();
+        let entity = DeleteImagePathError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -274,6 +440,16 @@ pub async fn get_image(
     configuration: &configuration::Configuration,
     id: &str,
 ) -> Result<models::MetadataResponse, Error<GetImageError>> {
+    get_image_with_response(configuration, id)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`get_image`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn get_image_with_response(
+    configuration: &configuration::Configuration,
+    id: &str,
+) -> Result<Response<models::MetadataResponse>, Error<GetImageError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_id = id;
 
@@ -287,14 +463,12 @@ pub async fn get_image(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -305,15 +479,18 @@ pub async fn get_image(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::MetadataResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::MetadataResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<GetImageError> = serde_json::from_str(&content).ok();
+        let entity = GetImageError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -327,6 +504,18 @@ pub async fn list_images(
     offset: Option<i32>,
     path: Option<&str>,
 ) -> Result<models::ImageListResponse, Error<ListImagesError>> {
+    list_images_with_response(configuration, limit, offset, path)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`list_images`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn list_images_with_response(
+    configuration: &configuration::Configuration,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    path: Option<&str>,
+) -> Result<Response<models::ImageListResponse>, Error<ListImagesError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_query_limit = limit;
     let p_query_offset = offset;
@@ -347,14 +536,12 @@ pub async fn list_images(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -365,15 +552,18 @@ pub async fn list_images(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::ImageListResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::ImageListResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<ListImagesError> = serde_json::from_str(&content).ok();
+        let entity = ListImagesError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -386,6 +576,17 @@ pub async fn search_images(
     q: &str,
     limit: Option<i32>,
 ) -> Result<models::SearchResponse, Error<SearchImagesError>> {
+    search_images_with_response(configuration, q, limit)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`search_images`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn search_images_with_response(
+    configuration: &configuration::Configuration,
+    q: &str,
+    limit: Option<i32>,
+) -> Result<Response<models::SearchResponse>, Error<SearchImagesError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_query_q = q;
     let p_query_limit = limit;
@@ -400,14 +601,12 @@ pub async fn search_images(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -418,15 +617,18 @@ pub async fn search_images(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::SearchResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::SearchResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<SearchImagesError> = serde_json::from_str(&content).ok();
+        let entity = SearchImagesError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -438,48 +640,200 @@ pub async fn upload_image(
     configuration: &configuration::Configuration,
     file: Option<std::path::PathBuf>,
     target_path: Option<&str>,
-    visibility: Option<&str>,
+    visibility: Option<models::Visibility>,
 ) -> Result<models::UploadResponse, Error<UploadImageError>> {
+    upload_image_with_response(configuration, file, target_path, visibility)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`upload_image`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn upload_image_with_response(
+    configuration: &configuration::Configuration,
+    file: Option<std::path::PathBuf>,
+    target_path: Option<&str>,
+    visibility: Option<models::Visibility>,
+) -> Result<Response<models::UploadResponse>, Error<UploadImageError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_form_file = file;
     let p_form_target_path = target_path;
     let p_form_visibility = visibility;
 
     let uri_str = format!("{}/api/v1/images", configuration.base_path);
-    let mut req_builder = configuration
-        .client
-        .request(reqwest::Method::POST, &uri_str);
+    // The file is streamed, so it is re-opened for every retry attempt.
+    let resp = super::retry::execute_with(configuration, || async {
+        let file_part = match p_form_file.as_deref() {
+            Some(path) => Some(upload_file_part(path).await?),
+            None => None,
+        };
+        Ok(upload_image_request(
+            configuration,
+            &uri_str,
+            file_part,
+            p_form_target_path,
+            p_form_visibility.as_ref(),
+        )?)
+    })
+    .await?;
+
+    upload_image_response(resp).await
+}
+
+/// Upload an image held in memory as `file_name` with the given `content_type`.
+pub async fn upload_image_bytes(
+    configuration: &configuration::Configuration,
+    data: impl Into<Bytes>,
+    file_name: &str,
+    content_type: &str,
+    target_path: Option<&str>,
+    visibility: Option<models::Visibility>,
+) -> Result<models::UploadResponse, Error<UploadImageError>> {
+    upload_image_bytes_with_response(
+        configuration,
+        data,
+        file_name,
+        content_type,
+        target_path,
+        visibility,
+    )
+    .await
+    .map(Response::into_entity)
+}
+
+/// Like [`upload_image_bytes`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn upload_image_bytes_with_response(
+    configuration: &configuration::Configuration,
+    data: impl Into<Bytes>,
+    file_name: &str,
+    content_type: &str,
+    target_path: Option<&str>,
+    visibility: Option<models::Visibility>,
+) -> Result<Response<models::UploadResponse>, Error<UploadImageError>> {
+    let data: Bytes = data.into();
+    let uri_str = format!("{}/api/v1/images", configuration.base_path);
+    // `Bytes` clones share the buffer, so every retry attempt resends it cheaply.
+    let resp = super::retry::execute_with(configuration, || {
+        let attempt = reqwest::multipart::Part::stream(data.clone())
+            .file_name(file_name.to_owned())
+            .mime_str(content_type)
+            .and_then(|part| {
+                upload_image_request(
+                    configuration,
+                    &uri_str,
+                    Some(part),
+                    target_path,
+                    visibility.as_ref(),
+                )
+            })
+            .map_err(Error::from);
+        std::future::ready(attempt)
+    })
+    .await?;
+
+    upload_image_response(resp).await
+}
+
+/// Upload an image streamed from `reader` as `file_name` with the given
+/// `content_type`. The body cannot be replayed, so the request is not retried.
+pub async fn upload_image_reader<R>(
+    configuration: &configuration::Configuration,
+    reader: R,
+    file_name: &str,
+    content_type: &str,
+    target_path: Option<&str>,
+    visibility: Option<models::Visibility>,
+) -> Result<models::UploadResponse, Error<UploadImageError>>
+where
+    R: AsyncRead + Send + 'static,
+{
+    upload_image_reader_with_response(
+        configuration,
+        reader,
+        file_name,
+        content_type,
+        target_path,
+        visibility,
+    )
+    .await
+    .map(Response::into_entity)
+}
+
+/// Like [`upload_image_reader`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn upload_image_reader_with_response<R>(
+    configuration: &configuration::Configuration,
+    reader: R,
+    file_name: &str,
+    content_type: &str,
+    target_path: Option<&str>,
+    visibility: Option<models::Visibility>,
+) -> Result<Response<models::UploadResponse>, Error<UploadImageError>>
+where
+    R: AsyncRead + Send + 'static,
+{
+    let uri_str = format!("{}/api/v1/images", configuration.base_path);
+    let stream = FramedRead::new(reader, BytesCodec::new());
+    let file_part = reqwest::multipart::Part::stream(reqwest::Body::wrap_stream(stream))
+        .file_name(file_name.to_owned())
+        .mime_str(content_type)?;
+    let req = upload_image_request(
+        configuration,
+        &uri_str,
+        Some(file_part),
+        target_path,
+        visibility.as_ref(),
+    )?;
+    let resp = super::retry::execute(configuration, req).await?;
+
+    upload_image_response(resp).await
+}
+
+/// Opens `path` as a streamed multipart part named after the file.
+async fn upload_file_part(
+    path: &std::path::Path,
+) -> Result<reqwest::multipart::Part, Error<UploadImageError>> {
+    let file = TokioFile::open(path).await?;
+    let stream = FramedRead::new(file, BytesCodec::new());
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(reqwest::multipart::Part::stream(reqwest::Body::wrap_stream(stream)).file_name(file_name))
+}
+
+/// Builds the multipart request shared by the `upload_image*` functions.
+fn upload_image_request(
+    configuration: &configuration::Configuration,
+    uri_str: &str,
+    file: Option<reqwest::multipart::Part>,
+    target_path: Option<&str>,
+    visibility: Option<&models::Visibility>,
+) -> Result<reqwest::Request, reqwest::Error> {
+    let mut req_builder = configuration.client.request(reqwest::Method::POST, uri_str);
 
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
     let mut multipart_form = reqwest::multipart::Form::new();
-    if let Some(ref param_value) = p_form_file {
-        let file = TokioFile::open(param_value).await?;
-        let stream = FramedRead::new(file, BytesCodec::new());
-        let file_name = param_value
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_default();
-        let file_part = reqwest::multipart::Part::stream(reqwest::Body::wrap_stream(stream))
-            .file_name(file_name);
+    if let Some(file_part) = file {
         multipart_form = multipart_form.part("file", file_part);
     }
-    if let Some(param_value) = p_form_target_path {
+    if let Some(param_value) = target_path {
         multipart_form = multipart_form.text("target_path", param_value.to_string());
     }
-    if let Some(param_value) = p_form_visibility {
+    if let Some(param_value) = visibility {
         multipart_form = multipart_form.text("visibility", param_value.to_string());
     }
     req_builder = req_builder.multipart(multipart_form);
 
-    let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    req_builder.build()
+}
 
+/// Parses the response shared by the `upload_image*` functions.
+async fn upload_image_response(
+    resp: reqwest::Response,
+) -> Result<Response<models::UploadResponse>, Error<UploadImageError>> {
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -490,15 +844,18 @@ pub async fn upload_image(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::UploadResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::UploadResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<UploadImageError> = serde_json::from_str(&content).ok();
+        let entity = UploadImageError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -511,6 +868,17 @@ pub async fn update_visibility(
     id: &str,
     update_visibility_request: models::UpdateVisibilityRequest,
 ) -> Result<models::UpdateVisibilityResponse, Error<UpdateVisibilityError>> {
+    update_visibility_with_response(configuration, id, update_visibility_request)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`update_visibility`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn update_visibility_with_response(
+    configuration: &configuration::Configuration,
+    id: &str,
+    update_visibility_request: models::UpdateVisibilityRequest,
+) -> Result<Response<models::UpdateVisibilityResponse>, Error<UpdateVisibilityError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_id = id;
     let p_body_update_visibility_request = update_visibility_request;
@@ -527,15 +895,13 @@ pub async fn update_visibility(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
     req_builder = req_builder.json(&p_body_update_visibility_request);
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -546,15 +912,18 @@ pub async fn update_visibility(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::UpdateVisibilityResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::UpdateVisibilityResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<UpdateVisibilityError> = serde_json::from_str(&content).ok();
+        let entity = UpdateVisibilityError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
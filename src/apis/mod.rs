@@ -4,15 +4,29 @@ use std::fmt;
 #[derive(Debug, Clone)]
 pub struct ResponseContent<T> {
     pub status: reqwest::StatusCode,
+    pub headers: reqwest::header::HeaderMap,
     pub content: String,
     pub entity: Option<T>,
 }
 
+impl<T> ResponseContent<T> {
+    /// Rate-limit state reported with the error, e.g. on a `429`.
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        RateLimitInfo::from_headers(&self.headers)
+    }
+
+    /// Identifier to quote in support requests, if the server sent one.
+    pub fn request_id(&self) -> Option<&str> {
+        response::request_id(&self.headers)
+    }
+}
+
 #[derive(Debug)]
 pub enum Error<T> {
     Reqwest(reqwest::Error),
     Serde(serde_json::Error),
     Io(std::io::Error),
+    Auth(auth::AuthError),
     ResponseError(ResponseContent<T>),
 }
 
@@ -22,6 +36,7 @@ impl<T> fmt::Display for Error<T> {
             Error::Reqwest(e) => ("reqwest", e.to_string()),
             Error::Serde(e) => ("serde", e.to_string()),
             Error::Io(e) => ("IO", e.to_string()),
+            Error::Auth(e) => ("auth", e.to_string()),
             Error::ResponseError(e) => ("response", format!("status code {}", e.status)),
         };
         write!(f, "error in {}: {}", module, e)
@@ -34,6 +49,7 @@ impl<T: fmt::Debug> error::Error for Error<T> {
             Error::Reqwest(e) => e,
             Error::Serde(e) => e,
             Error::Io(e) => e,
+            Error::Auth(e) => e,
             Error::ResponseError(_) => return None,
         })
     }
@@ -57,6 +73,34 @@ impl<T> From<std::io::Error> for Error<T> {
     }
 }
 
+impl<T> From<auth::AuthError> for Error<T> {
+    fn from(e: auth::AuthError) -> Self {
+        Error::Auth(e)
+    }
+}
+
+/// Typed error enum of an endpoint whose variant is chosen by the HTTP status
+/// of the error response, rather than by the first variant that deserializes.
+pub trait FromErrorResponse: Sized {
+    /// Returns the variant documented for `status`, or `None` if the endpoint
+    /// does not document that status.
+    fn from_status(status: reqwest::StatusCode, body: crate::models::ErrorResponse)
+        -> Option<Self>;
+
+    /// Wraps a body that is not a documented error response for its status.
+    fn unknown(value: serde_json::Value) -> Self;
+
+    /// Builds the typed entity for an error response, or `None` if `content`
+    /// is not JSON.
+    fn from_response(status: reqwest::StatusCode, content: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(content).ok()?;
+        let typed = serde_json::from_value(value.clone())
+            .ok()
+            .and_then(|body| Self::from_status(status, body));
+        Some(typed.unwrap_or_else(|| Self::unknown(value)))
+    }
+}
+
 pub fn urlencode<T: AsRef<str>>(s: T) -> String {
     ::url::form_urlencoded::byte_serialize(s.as_ref().as_bytes()).collect()
 }
@@ -101,6 +145,7 @@ enum ContentType {
     Unsupported(String),
 }
 
+#[allow(clippy::needless_return)]
 impl From<&str> for ContentType {
     fn from(content_type: &str) -> Self {
         if content_type.starts_with("application") && content_type.contains("json") {
@@ -113,9 +158,26 @@ impl From<&str> for ContentType {
     }
 }
 
+// The generated endpoint modules keep the generator's explicit returns.
+#[allow(clippy::needless_return)]
+pub mod api_keys_api;
+#[allow(clippy::needless_return)]
+pub mod health_api;
+#[allow(clippy::needless_return)]
 pub mod images_api;
+#[allow(clippy::needless_return)]
 pub mod presets_api;
+#[allow(clippy::needless_return)]
 pub mod settings_api;
+#[allow(clippy::needless_return)]
 pub mod usage_api;
 
+pub mod auth;
 pub mod configuration;
+pub mod credentials;
+pub mod rate_limit;
+pub mod response;
+pub mod retry;
+pub mod secret;
+
+pub use self::response::{RateLimitInfo, Response};
@@ -8,7 +8,7 @@
  * Generated by: https://openapi-generator.tech
  */
 
-use super::{configuration, ContentType, Error};
+use super::{configuration, ContentType, Error, FromErrorResponse, Response};
 use crate::{apis::ResponseContent, models};
 use reqwest;
 use serde::{de::Error as _, Deserialize, Serialize};
@@ -25,6 +25,23 @@ pub enum CreatePresetError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for CreatePresetError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            400 => Some(Self::Status400(body)),
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            409 => Some(Self::Status409(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`delete_preset`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -36,6 +53,22 @@ pub enum DeletePresetError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for DeletePresetError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            404 => Some(Self::Status404(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`get_preset`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -47,6 +80,22 @@ pub enum GetPresetError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for GetPresetError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            404 => Some(Self::Status404(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`list_presets`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -57,6 +106,21 @@ pub enum ListPresetsError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for ListPresetsError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`update_preset`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -70,11 +134,39 @@ pub enum UpdatePresetError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for UpdatePresetError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            400 => Some(Self::Status400(body)),
+            401 => Some(Self::Status401(body)),
+            403 => Some(Self::Status403(body)),
+            404 => Some(Self::Status404(body)),
+            409 => Some(Self::Status409(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// Creates a new transformation preset. Requires Pro plan.
 pub async fn create_preset(
     configuration: &configuration::Configuration,
     create_preset_request: Option<models::CreatePresetRequest>,
 ) -> Result<models::Preset, Error<CreatePresetError>> {
+    create_preset_with_response(configuration, create_preset_request)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`create_preset`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn create_preset_with_response(
+    configuration: &configuration::Configuration,
+    create_preset_request: Option<models::CreatePresetRequest>,
+) -> Result<Response<models::Preset>, Error<CreatePresetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_body_create_preset_request = create_preset_request;
 
@@ -86,15 +178,13 @@ pub async fn create_preset(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
     req_builder = req_builder.json(&p_body_create_preset_request);
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -105,15 +195,18 @@ pub async fn create_preset(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Preset`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Preset`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<CreatePresetError> = serde_json::from_str(&content).ok();
+        let entity = CreatePresetError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -125,6 +218,16 @@ pub async fn delete_preset(
     configuration: &configuration::Configuration,
     name: &str,
 ) -> Result<models::DeletePresetResponse, Error<DeletePresetError>> {
+    delete_preset_with_response(configuration, name)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`delete_preset`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn delete_preset_with_response(
+    configuration: &configuration::Configuration,
+    name: &str,
+) -> Result<Response<models::DeletePresetResponse>, Error<DeletePresetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_name = name;
 
@@ -140,14 +243,12 @@ pub async fn delete_preset(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -158,15 +259,18 @@ pub async fn delete_preset(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::DeletePresetResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::DeletePresetResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<DeletePresetError> = serde_json::from_str(&content).ok();
+        let entity = DeletePresetError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -178,6 +282,16 @@ pub async fn get_preset(
     configuration: &configuration::Configuration,
     id: &str,
 ) -> Result<models::Preset, Error<GetPresetError>> {
+    get_preset_with_response(configuration, id)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`get_preset`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn get_preset_with_response(
+    configuration: &configuration::Configuration,
+    id: &str,
+) -> Result<Response<models::Preset>, Error<GetPresetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_id = id;
 
@@ -191,14 +305,12 @@ pub async fn get_preset(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -209,15 +321,18 @@ pub async fn get_preset(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Preset`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Preset`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<GetPresetError> = serde_json::from_str(&content).ok();
+        let entity = GetPresetError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -228,20 +343,27 @@ pub async fn get_preset(
 pub async fn list_presets(
     configuration: &configuration::Configuration,
 ) -> Result<models::ListPresetsResponse, Error<ListPresetsError>> {
+    list_presets_with_response(configuration)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`list_presets`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn list_presets_with_response(
+    configuration: &configuration::Configuration,
+) -> Result<Response<models::ListPresetsResponse>, Error<ListPresetsError>> {
     let uri_str = format!("{}/api/v1/settings/presets", configuration.base_path);
     let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
 
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -252,15 +374,18 @@ pub async fn list_presets(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::ListPresetsResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::ListPresetsResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<ListPresetsError> = serde_json::from_str(&content).ok();
+        let entity = ListPresetsError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -273,6 +398,17 @@ pub async fn update_preset(
     id: &str,
     update_preset_request: Option<models::UpdatePresetRequest>,
 ) -> Result<models::Preset, Error<UpdatePresetError>> {
+    update_preset_with_response(configuration, id, update_preset_request)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`update_preset`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn update_preset_with_response(
+    configuration: &configuration::Configuration,
+    id: &str,
+    update_preset_request: Option<models::UpdatePresetRequest>,
+) -> Result<Response<models::Preset>, Error<UpdatePresetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_id = id;
     let p_body_update_preset_request = update_preset_request;
@@ -287,15 +423,13 @@ pub async fn update_preset(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
     req_builder = req_builder.json(&p_body_update_preset_request);
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -306,15 +440,18 @@ pub async fn update_preset(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Preset`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Preset`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<UpdatePresetError> = serde_json::from_str(&content).ok();
+        let entity = UpdatePresetError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
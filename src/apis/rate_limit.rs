@@ -0,0 +1,162 @@
+//! Client-side token-bucket rate limiting.
+//!
+//! A [`RateLimiter`] set on
+//! [`Configuration::rate_limiter`](super::configuration::Configuration::rate_limiter)
+//! is shared by every clone of the configuration. Each request takes a token
+//! before it is sent, so concurrent callers queue instead of running into
+//! `429 Too Many Requests`. The bucket is corrected from the `X-RateLimit-*`
+//! headers of every response.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::HeaderMap;
+
+use super::configuration::Configuration;
+use super::response::RateLimitInfo;
+use super::{usage_api, Error};
+use crate::models;
+
+/// img-src plans with a documented request rate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Plan {
+    /// 100 requests per minute.
+    Free,
+    /// 500 requests per minute.
+    Pro,
+}
+
+impl Plan {
+    /// Requests per minute allowed on this plan.
+    pub fn requests_per_minute(self) -> u32 {
+        match self {
+            Plan::Free => 100,
+            Plan::Pro => 500,
+        }
+    }
+
+    /// Parses a plan ID such as [`models::UsageResponse::plan`].
+    pub fn from_plan_id(plan: &str) -> Option<Plan> {
+        match plan.trim().to_ascii_lowercase().as_str() {
+            "free" => Some(Plan::Free),
+            "pro" => Some(Plan::Pro),
+            _ => None,
+        }
+    }
+}
+
+/// Token bucket holding at most one minute's worth of requests.
+#[derive(Debug)]
+pub struct RateLimiter {
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    /// May go negative: each queued caller reserves a token in advance.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn refill_per_sec(&self) -> f64 {
+        self.capacity / 60.0
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec()).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `requests_per_minute` requests, starting full.
+    pub fn new(requests_per_minute: u32) -> RateLimiter {
+        let capacity = f64::from(requests_per_minute.max(1));
+        RateLimiter {
+            bucket: Mutex::new(Bucket {
+                capacity,
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Creates a limiter for the documented rate of `plan`.
+    pub fn for_plan(plan: Plan) -> RateLimiter {
+        RateLimiter::new(plan.requests_per_minute())
+    }
+
+    /// Creates a limiter for the plan in `usage`, assuming the Free plan's
+    /// rate when the plan is not recognized.
+    pub fn from_usage(usage: &models::UsageResponse) -> RateLimiter {
+        RateLimiter::for_plan(Plan::from_plan_id(&usage.plan).unwrap_or(Plan::Free))
+    }
+
+    /// Fetches the account's usage and creates a limiter for its plan.
+    pub async fn detect(
+        configuration: &Configuration,
+    ) -> Result<RateLimiter, Error<usage_api::GetUsageError>> {
+        let usage = usage_api::get_usage(configuration).await?;
+        Ok(RateLimiter::from_usage(&usage))
+    }
+
+    /// Requests per minute currently enforced.
+    pub fn requests_per_minute(&self) -> u32 {
+        self.lock().capacity as u32
+    }
+
+    /// Waits until a request may be sent and takes a token for it.
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.lock();
+            bucket.refill(Instant::now());
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / bucket.refill_per_sec())
+        };
+        tokio::time::sleep(wait).await;
+    }
+
+    /// Adjusts the bucket to the rate-limit state reported by the server.
+    pub fn update(&self, info: &RateLimitInfo) {
+        let mut bucket = self.lock();
+        let now = Instant::now();
+        bucket.refill(now);
+        if info.limit > 0 {
+            bucket.capacity = f64::from(info.limit);
+        }
+        bucket.tokens = bucket.tokens.min(f64::from(info.remaining));
+        if info.remaining == 0 {
+            if let Some(until_reset) = info.reset.and_then(seconds_until_reset) {
+                // Hold the next token back until the server's window resets.
+                let held = 1.0 - until_reset * bucket.refill_per_sec();
+                bucket.tokens = bucket.tokens.min(held);
+            }
+        }
+    }
+
+    /// Adjusts the bucket from response headers, if they carry rate-limit state.
+    pub fn update_from_headers(&self, headers: &HeaderMap) {
+        if let Some(info) = RateLimitInfo::from_headers(headers) {
+            self.update(&info);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Bucket> {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Seconds until `reset`, which is a Unix timestamp or a relative delay.
+fn seconds_until_reset(reset: u64) -> Option<f64> {
+    if reset < 1_000_000_000 {
+        return Some(reset as f64);
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+    Some(Duration::from_secs(reset).saturating_sub(now).as_secs_f64())
+}
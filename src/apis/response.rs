@@ -0,0 +1,78 @@
+//! Response metadata returned by the `*_with_response` API functions.
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+/// Header carrying the request quota of the current rate-limit window.
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "x-ratelimit-limit";
+/// Header carrying the number of requests left in the current window.
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+/// Header carrying the time at which the current rate-limit window resets.
+pub const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+/// Headers that may identify a request, in order of preference.
+pub const REQUEST_ID_HEADERS: [&str; 2] = ["x-request-id", "cf-ray"];
+
+/// A parsed response body together with the HTTP metadata it arrived with.
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    /// Rate-limit state reported by the server, if the headers were present.
+    pub rate_limit: Option<RateLimitInfo>,
+    /// Identifier to quote in support requests, if the server sent one.
+    pub request_id: Option<String>,
+    pub entity: T,
+}
+
+impl<T> Response<T> {
+    pub fn new(status: StatusCode, headers: HeaderMap, entity: T) -> Response<T> {
+        Response {
+            status,
+            rate_limit: RateLimitInfo::from_headers(&headers),
+            request_id: request_id(&headers).map(str::to_owned),
+            headers,
+            entity,
+        }
+    }
+
+    /// Discards the metadata and returns the parsed body.
+    pub fn into_entity(self) -> T {
+        self.entity
+    }
+}
+
+/// Rate-limit state parsed from the `X-RateLimit-*` response headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Requests allowed per window.
+    pub limit: u32,
+    /// Requests left in the current window.
+    pub remaining: u32,
+    /// When the window resets, as sent by the server (usually a Unix timestamp).
+    pub reset: Option<u64>,
+}
+
+impl RateLimitInfo {
+    /// Parses the rate-limit headers, or returns `None` if limit or remaining
+    /// is missing or malformed.
+    pub fn from_headers(headers: &HeaderMap) -> Option<RateLimitInfo> {
+        Some(RateLimitInfo {
+            limit: header_number(headers, RATE_LIMIT_LIMIT_HEADER)?,
+            remaining: header_number(headers, RATE_LIMIT_REMAINING_HEADER)?,
+            reset: header_number(headers, RATE_LIMIT_RESET_HEADER),
+        })
+    }
+}
+
+pub(crate) fn header_number<N: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<N> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<&str> {
+    REQUEST_ID_HEADERS
+        .iter()
+        .find_map(|name| headers.get(*name)?.to_str().ok())
+}
@@ -0,0 +1,282 @@
+//! Automatic retries with exponential backoff.
+//!
+//! Every request in [`crate::apis`] goes through `execute` or
+//! `execute_with`, which re-send the request according to
+//! [`Configuration::retry_policy`](super::configuration::Configuration::retry_policy).
+
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+
+use super::auth;
+use super::configuration::Configuration;
+use super::response::{header_number, RATE_LIMIT_REMAINING_HEADER, RATE_LIMIT_RESET_HEADER};
+use super::Error;
+
+/// When and how failed requests are re-sent.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry. Doubles on every further retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for a single delay. A `Retry-After` or rate-limit reset
+    /// hint longer than this is not waited for; the response is returned.
+    pub max_backoff: Duration,
+    /// Randomize each computed backoff between half and all of its value, so
+    /// concurrent callers do not retry in lockstep.
+    pub jitter: bool,
+    /// Response statuses that are retried.
+    pub retry_statuses: Vec<StatusCode>,
+    /// Request methods that are retried. `429 Too Many Requests` is retried
+    /// for every method, since the server rejected the request unprocessed.
+    pub retry_methods: Vec<Method>,
+    /// Retry requests that fail to connect or time out.
+    pub retry_connection_errors: bool,
+    /// Wait as long as the `Retry-After` and `X-RateLimit-Reset` headers ask.
+    pub respect_retry_after: bool,
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn disabled() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Backoff before retry number `retry` (starting at 1), without jitter.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    fn retries_method(&self, method: &Method) -> bool {
+        self.retry_methods.contains(method)
+    }
+
+    fn retries_status(&self, method: &Method, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status)
+            && (status == StatusCode::TOO_MANY_REQUESTS || self.retries_method(method))
+    }
+
+    /// Delay before retry number `retry`, or `None` if the server asked for a
+    /// longer wait than [`RetryPolicy::max_backoff`].
+    fn delay(&self, retry: u32, headers: Option<&HeaderMap>) -> Option<Duration> {
+        if self.respect_retry_after {
+            if let Some(hint) = headers.and_then(server_delay) {
+                return (hint <= self.max_backoff).then_some(hint);
+            }
+        }
+        let backoff = self.backoff(retry);
+        if !self.jitter {
+            return Some(backoff);
+        }
+        let half = backoff / 2;
+        Some(half + half.mul_f64(random_fraction()))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(60),
+            jitter: true,
+            retry_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_methods: vec![
+                Method::GET,
+                Method::HEAD,
+                Method::OPTIONS,
+                Method::PUT,
+                Method::DELETE,
+            ],
+            retry_connection_errors: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+/// Wait requested by the server through `Retry-After` (delta seconds or an
+/// HTTP date) or, when the window is exhausted, `X-RateLimit-Reset` (Unix
+/// timestamp or seconds).
+fn server_delay(headers: &HeaderMap) -> Option<Duration> {
+    if let Some(delay) = retry_after(headers) {
+        return Some(delay);
+    }
+    let header = |name| header_number::<u64>(headers, name);
+    if header(RATE_LIMIT_REMAINING_HEADER) != Some(0) {
+        return None;
+    }
+    let reset = header(RATE_LIMIT_RESET_HEADER)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    // Values past 2001-09-09 are timestamps; smaller ones are relative seconds.
+    if reset >= 1_000_000_000 {
+        Some(Duration::from_secs(reset.saturating_sub(now)))
+    } else {
+        Some(Duration::from_secs(reset))
+    }
+}
+
+/// `Retry-After` as delta seconds or an HTTP date; a date in the past means no
+/// wait.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Uniformly distributed value in `[0, 1)`, seeded from the std hasher keys.
+fn random_fraction() -> f64 {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Sends `request` with the configured credentials, retrying according to the
+/// configured policy. Requests with a streaming body cannot be replayed and
+/// are sent once.
+pub(crate) async fn execute<T>(
+    configuration: &Configuration,
+    request: reqwest::Request,
+) -> Result<reqwest::Response, Error<T>> {
+    execute_request(configuration, true, request).await
+}
+
+/// Like [`execute`], but sends no credentials.
+pub(crate) async fn execute_unauthenticated<T>(
+    configuration: &Configuration,
+    request: reqwest::Request,
+) -> Result<reqwest::Response, Error<T>> {
+    execute_request(configuration, false, request).await
+}
+
+async fn execute_request<T>(
+    configuration: &Configuration,
+    authenticated: bool,
+    mut request: reqwest::Request,
+) -> Result<reqwest::Response, Error<T>> {
+    if request.try_clone().is_none() {
+        if authenticated {
+            auth::authenticate(configuration, &mut request).await?;
+        }
+        return Ok(send(configuration, request).await?);
+    }
+    run(configuration, authenticated, || {
+        let attempt = request
+            .try_clone()
+            .ok_or_else(|| Error::Io(std::io::Error::other("request body cannot be replayed")));
+        std::future::ready(attempt)
+    })
+    .await
+}
+
+/// Sends the request produced by `build` with the configured credentials,
+/// calling it again for every retry.
+pub(crate) async fn execute_with<T, F, Fut>(
+    configuration: &Configuration,
+    build: F,
+) -> Result<reqwest::Response, Error<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<reqwest::Request, Error<T>>>,
+{
+    run(configuration, true, build).await
+}
+
+async fn run<T, F, Fut>(
+    configuration: &Configuration,
+    authenticated: bool,
+    mut build: F,
+) -> Result<reqwest::Response, Error<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<reqwest::Request, Error<T>>>,
+{
+    let policy = &configuration.retry_policy;
+    let mut attempt = 1;
+    let mut refreshed = false;
+    loop {
+        let mut request = build().await?;
+        let provided = if authenticated {
+            auth::authenticate(configuration, &mut request).await?
+        } else {
+            None
+        };
+        let method = request.method().clone();
+        let can_retry = attempt < policy.max_attempts;
+        match send(configuration, request).await {
+            Ok(resp) => {
+                // One refresh per request, outside the retry budget.
+                if resp.status() == StatusCode::UNAUTHORIZED && !refreshed {
+                    if let (Some(provider), Some(token)) =
+                        (&configuration.credential_provider, &provided)
+                    {
+                        refreshed = true;
+                        if provider.refresh(token).await? {
+                            continue;
+                        }
+                    }
+                }
+                if can_retry && policy.retries_status(&method, resp.status()) {
+                    if let Some(delay) = policy.delay(attempt, Some(resp.headers())) {
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+                }
+                return Ok(resp);
+            }
+            Err(e) => {
+                if can_retry
+                    && policy.retries_method(&method)
+                    && policy.retry_connection_errors
+                    && (e.is_connect() || e.is_timeout())
+                {
+                    if let Some(delay) = policy.delay(attempt, None) {
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+                }
+                return Err(Error::from(e));
+            }
+        }
+    }
+}
+
+/// Sends a single attempt, paced by the configured rate limiter.
+async fn send(
+    configuration: &Configuration,
+    request: reqwest::Request,
+) -> Result<reqwest::Response, reqwest::Error> {
+    if let Some(ref limiter) = configuration.rate_limiter {
+        limiter.acquire().await;
+    }
+    let resp = configuration.client.execute(request).await?;
+    if let Some(ref limiter) = configuration.rate_limiter {
+        limiter.update_from_headers(resp.headers());
+    }
+    Ok(resp)
+}
@@ -0,0 +1,86 @@
+//! Credentials that stay out of logs.
+//!
+//! The credential fields of
+//! [`Configuration`](super::configuration::Configuration) hold a [`Secret`],
+//! whose `Debug` output shows only the `imgsrc_` key prefix and a short
+//! fingerprint. Two redacted keys can still be told apart in a log without
+//! revealing either of them. With the `zeroize` feature the value is also
+//! overwritten in memory when it is dropped.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+/// Prefix of img-src API keys, shown in redacted output.
+const KEY_PREFIX: &str = "imgsrc_";
+
+/// A credential whose `Debug` output is redacted.
+///
+/// `Secret` deliberately does not implement `Display`; use
+/// [`expose_secret`](Secret::expose_secret) where the value itself is needed.
+/// It serializes as the plain string, so a model holding one can still be
+/// sent and stored.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Secret {
+        Secret(value.into())
+    }
+
+    /// The plain-text value.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// First 8 hex digits of the SHA-256 of the value.
+    pub fn fingerprint(&self) -> String {
+        Sha256::digest(self.0.as_bytes())[..4]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = if self.0.starts_with(KEY_PREFIX) {
+            KEY_PREFIX
+        } else {
+            ""
+        };
+        write!(f, "Secret({prefix}***#{})", self.fingerprint())
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret(value.to_owned())
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Secret, D::Error> {
+        String::deserialize(deserializer).map(Secret)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Secret {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
@@ -8,7 +8,7 @@
  * Generated by: https://openapi-generator.tech
  */
 
-use super::{configuration, ContentType, Error};
+use super::{configuration, ContentType, Error, FromErrorResponse, Response};
 use crate::{apis::ResponseContent, models};
 use reqwest;
 use serde::{de::Error as _, Deserialize, Serialize};
@@ -22,6 +22,20 @@ pub enum GetSettingsError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for GetSettingsError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// struct for typed errors of method [`update_settings`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -33,24 +47,47 @@ pub enum UpdateSettingsError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for UpdateSettingsError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            400 => Some(Self::Status400(body)),
+            401 => Some(Self::Status401(body)),
+            404 => Some(Self::Status404(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// Returns the authenticated user's settings
 pub async fn get_settings(
     configuration: &configuration::Configuration,
 ) -> Result<models::SettingsResponse, Error<GetSettingsError>> {
+    get_settings_with_response(configuration)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`get_settings`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn get_settings_with_response(
+    configuration: &configuration::Configuration,
+) -> Result<Response<models::SettingsResponse>, Error<GetSettingsError>> {
     let uri_str = format!("{}/api/v1/settings", configuration.base_path);
     let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
 
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -61,15 +98,18 @@ pub async fn get_settings(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::SettingsResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::SettingsResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<GetSettingsError> = serde_json::from_str(&content).ok();
+        let entity = GetSettingsError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -81,6 +121,16 @@ pub async fn update_settings(
     configuration: &configuration::Configuration,
     update_settings_request: Option<models::UpdateSettingsRequest>,
 ) -> Result<models::SettingsUpdateResponse, Error<UpdateSettingsError>> {
+    update_settings_with_response(configuration, update_settings_request)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`update_settings`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn update_settings_with_response(
+    configuration: &configuration::Configuration,
+    update_settings_request: Option<models::UpdateSettingsRequest>,
+) -> Result<Response<models::SettingsUpdateResponse>, Error<UpdateSettingsError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_body_update_settings_request = update_settings_request;
 
@@ -90,15 +140,13 @@ pub async fn update_settings(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
     req_builder = req_builder.json(&p_body_update_settings_request);
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -109,15 +157,18 @@ pub async fn update_settings(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::SettingsUpdateResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::SettingsUpdateResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<UpdateSettingsError> = serde_json::from_str(&content).ok();
+        let entity = UpdateSettingsError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
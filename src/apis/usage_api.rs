@@ -8,7 +8,7 @@
  * Generated by: https://openapi-generator.tech
  */
 
-use super::{configuration, ContentType, Error};
+use super::{configuration, ContentType, Error, FromErrorResponse, Response};
 use crate::{apis::ResponseContent, models};
 use reqwest;
 use serde::{de::Error as _, Deserialize, Serialize};
@@ -22,24 +22,45 @@ pub enum GetUsageError {
     UnknownValue(serde_json::Value),
 }
 
+impl FromErrorResponse for GetUsageError {
+    fn from_status(status: reqwest::StatusCode, body: models::ErrorResponse) -> Option<Self> {
+        match status.as_u16() {
+            401 => Some(Self::Status401(body)),
+            500 => Some(Self::Status500(body)),
+            _ => None,
+        }
+    }
+
+    fn unknown(value: serde_json::Value) -> Self {
+        Self::UnknownValue(value)
+    }
+}
+
 /// Returns usage statistics for the authenticated user
 pub async fn get_usage(
     configuration: &configuration::Configuration,
 ) -> Result<models::UsageResponse, Error<GetUsageError>> {
+    get_usage_with_response(configuration)
+        .await
+        .map(Response::into_entity)
+}
+
+/// Like [`get_usage`], but also returns the status, headers, rate-limit state and request ID.
+pub async fn get_usage_with_response(
+    configuration: &configuration::Configuration,
+) -> Result<Response<models::UsageResponse>, Error<GetUsageError>> {
     let uri_str = format!("{}/api/v1/usage", configuration.base_path);
     let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
 
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
-    if let Some(ref token) = configuration.bearer_access_token {
-        req_builder = req_builder.bearer_auth(token.to_owned());
-    };
 
     let req = req_builder.build()?;
-    let resp = configuration.client.execute(req).await?;
+    let resp = super::retry::execute(configuration, req).await?;
 
     let status = resp.status();
+    let headers = resp.headers().clone();
     let content_type = resp
         .headers()
         .get("content-type")
@@ -50,15 +71,18 @@ pub async fn get_usage(
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
-            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Json => serde_json::from_str(&content)
+                .map(|entity| Response::new(status, headers, entity))
+                .map_err(Error::from),
             ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::UsageResponse`"))),
             ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::UsageResponse`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<GetUsageError> = serde_json::from_str(&content).ok();
+        let entity = GetUsageError::from_response(status, &content);
         Err(Error::ResponseError(ResponseContent {
             status,
+            headers,
             content,
             entity,
         }))
@@ -0,0 +1,595 @@
+//! Bulk operations over many images.
+//!
+//! [`upload_dir`] uploads every image below a local directory with a bounded
+//! number of concurrent requests and reports the outcome for each file.
+//! [`delete_many`] removes a batch of images or paths the same way.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use reqwest::StatusCode;
+
+use crate::apis::configuration::Configuration;
+use crate::apis::images_api::{self, ListImagesError, UploadImageError};
+use crate::apis::settings_api::{self, GetSettingsError};
+use crate::apis::Error;
+use crate::dedup::{self, HashIndex, PrecheckedUpload};
+use crate::error::ImgSrcError;
+use crate::models;
+use crate::pagination::{self, ListImagesOptions};
+
+/// File extensions [`upload_dir`] uploads by default.
+pub const DEFAULT_IMAGE_EXTENSIONS: [&str; 9] = [
+    "jpg", "jpeg", "png", "webp", "avif", "gif", "bmp", "tiff", "jxl",
+];
+
+/// How [`upload_dir`] selects and uploads files.
+#[derive(Debug, Clone)]
+pub struct UploadDirOptions {
+    /// Folder prepended to every `target_path`.
+    pub target_prefix: Option<String>,
+    /// Visibility sent with every upload.
+    pub visibility: Option<models::Visibility>,
+    /// Maximum number of uploads in flight.
+    pub concurrency: usize,
+    /// Extensions (without the dot, case-insensitive) to upload; other files
+    /// are reported as skipped.
+    pub extensions: Vec<String>,
+    /// Upload files and descend into directories whose name starts with `.`.
+    pub include_hidden: bool,
+    /// Hash files locally and skip those this index shows are already
+    /// published at their target path. Uploads are recorded in the index.
+    pub hash_index: Option<Arc<HashIndex>>,
+}
+
+impl Default for UploadDirOptions {
+    fn default() -> Self {
+        UploadDirOptions {
+            target_prefix: None,
+            visibility: None,
+            concurrency: 4,
+            extensions: DEFAULT_IMAGE_EXTENSIONS.map(String::from).to_vec(),
+            include_hidden: false,
+            hash_index: None,
+        }
+    }
+}
+
+/// Why [`upload_dir`] did not upload a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The file or one of its directories is hidden.
+    Hidden,
+    /// The extension is not in [`UploadDirOptions::extensions`].
+    UnsupportedExtension,
+    /// Symbolic links are not followed.
+    Symlink,
+}
+
+/// Result of uploading a single file.
+#[derive(Debug)]
+pub enum UploadOutcome {
+    /// The image was stored for the first time.
+    New(Box<models::UploadResponse>),
+    /// The content already existed (`is_new == false`); the path was added to it.
+    Deduplicated(Box<models::UploadResponse>),
+    /// The local hash pre-check found the content already published at the
+    /// target path, so nothing was sent.
+    AlreadyUploaded { id: String, hash: String },
+    /// The file was not uploaded.
+    Skipped(SkipReason),
+    /// The upload failed.
+    Failed(Error<UploadImageError>),
+}
+
+/// Outcome for one file found by [`upload_dir`].
+#[derive(Debug)]
+pub struct FileUpload {
+    /// Local path of the file.
+    pub path: PathBuf,
+    /// Path relative to the uploaded directory, with `/` separators.
+    pub relative_path: String,
+    /// Folder the file was uploaded into.
+    pub target_path: Option<String>,
+    pub outcome: UploadOutcome,
+}
+
+/// Per-file results of [`upload_dir`], ordered by relative path.
+#[derive(Debug, Default)]
+pub struct UploadReport {
+    pub files: Vec<FileUpload>,
+}
+
+impl UploadReport {
+    /// Files stored as new images.
+    pub fn new_images(&self) -> impl Iterator<Item = &FileUpload> {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.outcome, UploadOutcome::New(_)))
+    }
+
+    /// Files whose content was already stored.
+    pub fn deduplicated(&self) -> impl Iterator<Item = &FileUpload> {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.outcome, UploadOutcome::Deduplicated(_)))
+    }
+
+    /// Files the hash pre-check found already published.
+    pub fn already_uploaded(&self) -> impl Iterator<Item = &FileUpload> {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.outcome, UploadOutcome::AlreadyUploaded { .. }))
+    }
+
+    /// Files that were not uploaded.
+    pub fn skipped(&self) -> impl Iterator<Item = &FileUpload> {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.outcome, UploadOutcome::Skipped(_)))
+    }
+
+    /// Files whose upload failed.
+    pub fn failed(&self) -> impl Iterator<Item = &FileUpload> {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.outcome, UploadOutcome::Failed(_)))
+    }
+
+    /// Whether every upload that was attempted succeeded.
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+/// Uploads every image below `dir`.
+///
+/// The directory part of each file's path relative to `dir`, under
+/// [`UploadDirOptions::target_prefix`], becomes its `target_path`; the server
+/// keeps the file name. Failed uploads are recorded in the report; only
+/// errors reading the directory tree itself are returned.
+pub async fn upload_dir(
+    configuration: &Configuration,
+    dir: impl AsRef<Path>,
+    options: &UploadDirOptions,
+) -> io::Result<UploadReport> {
+    let mut report = UploadReport::default();
+    let mut pending = Vec::new();
+    for file in collect_files(dir.as_ref(), options).await? {
+        match file.skip {
+            Some(reason) => report.files.push(FileUpload {
+                path: file.path,
+                relative_path: file.relative_path,
+                target_path: file.target_path,
+                outcome: UploadOutcome::Skipped(reason),
+            }),
+            None => pending.push(file),
+        }
+    }
+
+    let visibility = options.visibility.as_ref();
+    let uploads = stream::iter(pending)
+        .map(|file| async move {
+            let outcome = upload_file(
+                configuration,
+                options.hash_index.as_deref(),
+                &file,
+                visibility,
+            )
+            .await;
+            FileUpload {
+                path: file.path,
+                relative_path: file.relative_path,
+                target_path: file.target_path,
+                outcome,
+            }
+        })
+        .buffer_unordered(options.concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+    report.files.extend(uploads);
+    report
+        .files
+        .sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    Ok(report)
+}
+
+async fn upload_file(
+    configuration: &Configuration,
+    hash_index: Option<&HashIndex>,
+    file: &LocalFile,
+    visibility: Option<&models::Visibility>,
+) -> UploadOutcome {
+    let path = file.path.clone();
+    let target_path = file.target_path.as_deref();
+    let result = match hash_index {
+        Some(index) => {
+            dedup::upload_image_if_new(configuration, index, path, target_path, visibility.cloned())
+                .await
+        }
+        None => {
+            images_api::upload_image(configuration, Some(path), target_path, visibility.cloned())
+                .await
+                .map(|upload| PrecheckedUpload::Uploaded(Box::new(upload)))
+        }
+    };
+    match result {
+        Ok(PrecheckedUpload::AlreadyUploaded { id, hash }) => {
+            UploadOutcome::AlreadyUploaded { id, hash }
+        }
+        Ok(PrecheckedUpload::Uploaded(upload)) if upload.is_new == Some(false) => {
+            UploadOutcome::Deduplicated(upload)
+        }
+        Ok(PrecheckedUpload::Uploaded(upload)) => UploadOutcome::New(upload),
+        Err(e) => UploadOutcome::Failed(e),
+    }
+}
+
+/// A file found below the directory being uploaded.
+pub(crate) struct LocalFile {
+    pub(crate) path: PathBuf,
+    pub(crate) relative_path: String,
+    pub(crate) target_path: Option<String>,
+    pub(crate) skip: Option<SkipReason>,
+}
+
+/// Lists every file below `root`, sorted by relative path, marking the ones
+/// `options` excludes.
+pub(crate) async fn collect_files(
+    root: &Path,
+    options: &UploadDirOptions,
+) -> io::Result<Vec<LocalFile>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![(root.to_path_buf(), Vec::<String>::new(), false)];
+    while let Some((dir, segments, hidden_dir)) = dirs.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let hidden = hidden_dir || name.starts_with('.');
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                if hidden && !options.include_hidden {
+                    continue;
+                }
+                let mut segments = segments.clone();
+                segments.push(name);
+                dirs.push((entry.path(), segments, hidden));
+                continue;
+            }
+
+            let skip = if file_type.is_symlink() {
+                Some(SkipReason::Symlink)
+            } else if hidden && !options.include_hidden {
+                Some(SkipReason::Hidden)
+            } else if !has_extension(&name, &options.extensions) {
+                Some(SkipReason::UnsupportedExtension)
+            } else {
+                None
+            };
+            let folder: Vec<&str> = options
+                .target_prefix
+                .iter()
+                .map(|prefix| prefix.trim_matches('/'))
+                .filter(|prefix| !prefix.is_empty())
+                .chain(segments.iter().map(String::as_str))
+                .collect();
+            let mut relative = segments.clone();
+            relative.push(name);
+            files.push(LocalFile {
+                path: entry.path(),
+                relative_path: relative.join("/"),
+                target_path: (!folder.is_empty()).then(|| folder.join("/")),
+                skip,
+            });
+        }
+    }
+    files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    Ok(files)
+}
+
+fn has_extension(name: &str, extensions: &[String]) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+}
+
+/// An image or a single path of an image to delete.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeleteTarget {
+    /// The whole image with this ID, with all its paths.
+    Image(String),
+    /// One path (e.g. `photos/2024/cat.png`); the image is deleted with its
+    /// last path.
+    Path(String),
+}
+
+/// How [`delete_many`] deletes.
+#[derive(Debug, Clone)]
+pub struct BulkDeleteOptions {
+    /// Maximum number of deletions, or dry-run lookups, in flight.
+    pub concurrency: usize,
+    /// Look every target up and report whether it would be deleted, without
+    /// deleting anything.
+    pub dry_run: bool,
+    /// Account username for path deletion; fetched from the settings when
+    /// `None` and needed.
+    pub username: Option<String>,
+}
+
+impl Default for BulkDeleteOptions {
+    fn default() -> Self {
+        BulkDeleteOptions {
+            concurrency: 4,
+            dry_run: false,
+            username: None,
+        }
+    }
+}
+
+/// Result of deleting a single target.
+#[derive(Debug)]
+pub enum DeleteOutcome {
+    /// Dry run: the target exists and would be deleted.
+    Planned,
+    /// The image was deleted.
+    ImageDeleted(Box<models::DeleteResponse>),
+    /// The path was deleted; see `remaining_paths` and `image_deleted`.
+    PathDeleted(Box<models::PathDeleteResponse>),
+    /// The image or path did not exist.
+    NotFound,
+    /// Deleting the target, or looking it up in a dry run, failed.
+    Failed(Box<DeleteFailure>),
+}
+
+/// A target [`delete_many`] could not delete or look up.
+#[derive(Debug)]
+pub struct DeleteFailure {
+    /// The path without surrounding slashes, or the ID of an image target.
+    pub path: String,
+    pub error: ImgSrcError,
+}
+
+/// Outcome for one target of [`delete_many`].
+#[derive(Debug)]
+pub struct DeleteResult {
+    pub target: DeleteTarget,
+    pub outcome: DeleteOutcome,
+}
+
+/// Per-target results of [`delete_many`], in input order.
+#[derive(Debug, Default)]
+pub struct DeleteReport {
+    pub results: Vec<DeleteResult>,
+}
+
+impl DeleteReport {
+    /// Targets that were deleted.
+    pub fn deleted(&self) -> impl Iterator<Item = &DeleteResult> {
+        self.results.iter().filter(|r| {
+            matches!(
+                r.outcome,
+                DeleteOutcome::ImageDeleted(_) | DeleteOutcome::PathDeleted(_)
+            )
+        })
+    }
+
+    /// Targets that would be deleted in a dry run.
+    pub fn planned(&self) -> impl Iterator<Item = &DeleteResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, DeleteOutcome::Planned))
+    }
+
+    /// Targets that did not exist.
+    pub fn not_found(&self) -> impl Iterator<Item = &DeleteResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, DeleteOutcome::NotFound))
+    }
+
+    /// Targets whose deletion or lookup failed.
+    pub fn failed(&self) -> impl Iterator<Item = &DeleteResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, DeleteOutcome::Failed(_)))
+    }
+
+    /// The failures, in input order.
+    pub fn failures(&self) -> impl Iterator<Item = &DeleteFailure> {
+        self.results.iter().filter_map(|r| match &r.outcome {
+            DeleteOutcome::Failed(failure) => Some(failure.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Number of images removed entirely, including those whose last path
+    /// was deleted.
+    pub fn images_deleted(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| match &r.outcome {
+                DeleteOutcome::ImageDeleted(_) => true,
+                DeleteOutcome::PathDeleted(resp) => resp.image_deleted,
+                _ => false,
+            })
+            .count()
+    }
+
+    /// Deleted paths whose image still has other paths, with those paths.
+    pub fn remaining_paths(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.results
+            .iter()
+            .filter_map(|r| match (&r.target, &r.outcome) {
+                (DeleteTarget::Path(path), DeleteOutcome::PathDeleted(resp))
+                    if !resp.image_deleted =>
+                {
+                    Some((path.as_str(), resp.remaining_paths.as_slice()))
+                }
+                _ => None,
+            })
+    }
+
+    /// Whether no deletion failed.
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+/// Deletes every target with a bounded number of concurrent requests.
+///
+/// Failed deletions are recorded in the report; only a failure to fetch the
+/// username needed for path deletion is returned. With
+/// [`BulkDeleteOptions::dry_run`], images are looked up by ID and paths in a
+/// listing of their folder, so the report predicts which targets would be
+/// deleted and which are not found.
+pub async fn delete_many(
+    configuration: &Configuration,
+    targets: impl IntoIterator<Item = DeleteTarget>,
+    options: &BulkDeleteOptions,
+) -> Result<DeleteReport, Error<GetSettingsError>> {
+    let targets: Vec<DeleteTarget> = targets.into_iter().collect();
+    let needs_username = targets.iter().any(|t| matches!(t, DeleteTarget::Path(_)));
+    let username = match &options.username {
+        Some(username) => username.clone(),
+        None if needs_username => {
+            settings_api::get_settings(configuration)
+                .await?
+                .settings
+                .username
+        }
+        None => String::new(),
+    };
+    let username = username.as_str();
+    if options.dry_run {
+        let results = plan(configuration, targets, username, options.concurrency).await;
+        return Ok(DeleteReport { results });
+    }
+
+    let results = stream::iter(targets)
+        .map(|target| async move {
+            let outcome = match &target {
+                DeleteTarget::Image(id) => {
+                    match images_api::delete_image(configuration, id).await {
+                        Ok(resp) => DeleteOutcome::ImageDeleted(Box::new(resp)),
+                        Err(e) if is_not_found(&e) => DeleteOutcome::NotFound,
+                        Err(e) => failed(id, e),
+                    }
+                }
+                DeleteTarget::Path(path) => {
+                    let path = path.trim_matches('/');
+                    match images_api::delete_image_path(configuration, username, path).await {
+                        Ok(resp) => DeleteOutcome::PathDeleted(Box::new(resp)),
+                        Err(e) if is_not_found(&e) => DeleteOutcome::NotFound,
+                        Err(e) => failed(path, e),
+                    }
+                }
+            };
+            DeleteResult { target, outcome }
+        })
+        .buffered(options.concurrency.max(1))
+        .collect()
+        .await;
+    Ok(DeleteReport { results })
+}
+
+/// Looks every target up without deleting it. Each folder holding a path
+/// target is listed once; a folder whose listing failed is listed again for
+/// each of its targets so every failure carries its own error.
+async fn plan(
+    configuration: &Configuration,
+    targets: Vec<DeleteTarget>,
+    username: &str,
+    concurrency: usize,
+) -> Vec<DeleteResult> {
+    let concurrency = concurrency.max(1);
+    let folders: HashSet<String> = targets
+        .iter()
+        .filter_map(|target| match target {
+            DeleteTarget::Path(path) => Some(parent(path.trim_matches('/')).to_owned()),
+            DeleteTarget::Image(_) => None,
+        })
+        .collect();
+    let listed: HashMap<String, HashSet<String>> = stream::iter(folders)
+        .map(|folder| async move {
+            let paths = published_paths(configuration, &folder, username).await;
+            paths.ok().map(|paths| (folder, paths))
+        })
+        .buffer_unordered(concurrency)
+        .filter_map(|listed| async move { listed })
+        .collect()
+        .await;
+    let listed = &listed;
+
+    stream::iter(targets)
+        .map(|target| async move {
+            let outcome = match &target {
+                DeleteTarget::Image(id) => match images_api::get_image(configuration, id).await {
+                    Ok(_) => DeleteOutcome::Planned,
+                    Err(e) if is_not_found(&e) => DeleteOutcome::NotFound,
+                    Err(e) => failed(id, e),
+                },
+                DeleteTarget::Path(path) => {
+                    let path = path.trim_matches('/');
+                    let folder = parent(path);
+                    let paths = match listed.get(folder) {
+                        Some(paths) => Ok(paths.contains(path)),
+                        None => published_paths(configuration, folder, username)
+                            .await
+                            .map(|paths| paths.contains(path)),
+                    };
+                    match paths {
+                        Ok(true) => DeleteOutcome::Planned,
+                        Ok(false) => DeleteOutcome::NotFound,
+                        Err(e) => failed(path, e),
+                    }
+                }
+            };
+            DeleteResult { target, outcome }
+        })
+        .buffered(concurrency)
+        .collect()
+        .await
+}
+
+/// Paths of the images listed in `folder`, relative to the account.
+async fn published_paths(
+    configuration: &Configuration,
+    folder: &str,
+    username: &str,
+) -> Result<HashSet<String>, Error<ListImagesError>> {
+    let filter = (!folder.is_empty()).then_some(folder);
+    let options = ListImagesOptions {
+        prefetch: 0,
+        ..ListImagesOptions::default()
+    };
+    let user_prefix = format!("{username}/");
+    pagination::list_images_stream(configuration, filter, options)
+        .try_fold(HashSet::new(), |mut paths, image| {
+            paths.extend(image.paths.iter().map(|p| {
+                let p = p.trim_matches('/');
+                p.strip_prefix(&user_prefix).unwrap_or(p).to_owned()
+            }));
+            async move { Ok(paths) }
+        })
+        .await
+}
+
+/// Folder of `path`, or `""` for the account root.
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(folder, _)| folder)
+}
+
+fn failed<T>(path: &str, error: Error<T>) -> DeleteOutcome {
+    DeleteOutcome::Failed(Box::new(DeleteFailure {
+        path: path.to_owned(),
+        error: error.into(),
+    }))
+}
+
+fn is_not_found<T>(e: &Error<T>) -> bool {
+    matches!(e, Error::ResponseError(content) if content.status == StatusCode::NOT_FOUND)
+}
@@ -0,0 +1,228 @@
+//! Typed builder for CDN transformation URLs.
+//!
+//! Images are served from `https://img-src.io/i/{username}/{path}` and
+//! transformed on demand through query parameters:
+//!
+//! ```
+//! use img_src::cdn::UrlBuilder;
+//! use img_src::models::{Fit, OutputFormat, Quality};
+//!
+//! let url = UrlBuilder::new("alice", "photos/2024/photo.jpg")
+//!     .width(800)
+//!     .height(600)
+//!     .fit(Fit::Cover)
+//!     .quality(Quality::new(85).unwrap())
+//!     .format(OutputFormat::Webp)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(
+//!     url,
+//!     "https://img-src.io/i/alice/photos/2024/photo.webp?w=800&h=600&fit=cover&q=85"
+//! );
+//! ```
+
+use std::error;
+use std::fmt;
+
+use url::Url;
+
+use crate::models::{self, Fit, OutputFormat, Quality};
+
+/// Origin serving images when no other base URL is set.
+pub const DEFAULT_BASE_URL: &str = "https://img-src.io";
+
+/// Error returned by [`UrlBuilder::build`] for options the CDN would reject.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// Width of zero.
+    Width(u32),
+    /// Height of zero.
+    Height(u32),
+    /// Quality outside `1..=100`, as read from a response.
+    Quality(i32),
+    /// Missing username or image path.
+    EmptyPath,
+    /// Base or image URL that could not be parsed.
+    InvalidUrl(String),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::Width(w) => write!(f, "width {w} is not a positive number"),
+            UrlError::Height(h) => write!(f, "height {h} is not a positive number"),
+            UrlError::Quality(q) => write!(f, "quality {q} is outside 1..=100"),
+            UrlError::EmptyPath => write!(f, "username and image path must not be empty"),
+            UrlError::InvalidUrl(url) => write!(f, "invalid URL: {url}"),
+        }
+    }
+}
+
+impl error::Error for UrlError {}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Path { username: String, path: String },
+    Url(String),
+}
+
+/// Builds a CDN URL for an image with optional transformations.
+#[derive(Debug, Clone)]
+pub struct UrlBuilder {
+    base_url: String,
+    source: Source,
+    width: Option<u32>,
+    height: Option<u32>,
+    fit: Option<Fit>,
+    quality: Option<Quality>,
+    format: Option<OutputFormat>,
+}
+
+impl UrlBuilder {
+    /// Starts a URL for the image at `path` under `username`.
+    pub fn new(username: impl Into<String>, path: impl Into<String>) -> UrlBuilder {
+        UrlBuilder::with_source(Source::Path {
+            username: username.into(),
+            path: path.into(),
+        })
+    }
+
+    /// Starts from an existing CDN URL. Any query string it carries is replaced.
+    pub fn from_url(url: impl Into<String>) -> UrlBuilder {
+        UrlBuilder::with_source(Source::Url(url.into()))
+    }
+
+    /// Starts from the primary URL of an uploaded image.
+    pub fn from_upload(upload: &models::UploadResponse) -> UrlBuilder {
+        UrlBuilder::from_url(upload.url.as_str())
+    }
+
+    /// Starts from the original-format URL of an image's metadata.
+    pub fn from_metadata(metadata: &models::MetadataResponse) -> UrlBuilder {
+        UrlBuilder::from_url(metadata.urls.original.as_str())
+    }
+
+    fn with_source(source: Source) -> UrlBuilder {
+        UrlBuilder {
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            source,
+            width: None,
+            height: None,
+            fit: None,
+            quality: None,
+            format: None,
+        }
+    }
+
+    /// Origin used with [`UrlBuilder::new`]. Defaults to [`DEFAULT_BASE_URL`].
+    pub fn base_url(mut self, base_url: impl Into<String>) -> UrlBuilder {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Target width in pixels (`w`).
+    pub fn width(mut self, width: u32) -> UrlBuilder {
+        self.width = Some(width);
+        self
+    }
+
+    /// Target height in pixels (`h`).
+    pub fn height(mut self, height: u32) -> UrlBuilder {
+        self.height = Some(height);
+        self
+    }
+
+    /// How the image is fitted to the target dimensions (`fit`).
+    pub fn fit(mut self, fit: Fit) -> UrlBuilder {
+        self.fit = Some(fit);
+        self
+    }
+
+    /// Output quality (`q`).
+    pub fn quality(mut self, quality: Quality) -> UrlBuilder {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// Output format, selected through the file extension.
+    pub fn format(mut self, format: OutputFormat) -> UrlBuilder {
+        self.format = Some(format);
+        self
+    }
+
+    /// Validates the options and returns the encoded URL.
+    pub fn build(&self) -> Result<String, UrlError> {
+        self.validate()?;
+
+        let mut url = match &self.source {
+            Source::Path { username, path } => {
+                let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+                if username.is_empty() || segments.is_empty() {
+                    return Err(UrlError::EmptyPath);
+                }
+                let mut url = parse(&self.base_url)?;
+                url.path_segments_mut()
+                    .map_err(|_| UrlError::InvalidUrl(self.base_url.clone()))?
+                    .pop_if_empty()
+                    .push("i")
+                    .push(username)
+                    .extend(segments);
+                url
+            }
+            Source::Url(url) => parse(url)?,
+        };
+        url.set_fragment(None);
+        url.set_query(None);
+
+        if let Some(ref format) = self.format {
+            let path = with_extension(url.path(), format.as_str());
+            url.set_path(&path);
+        }
+
+        let mut params: Vec<(&str, String)> = Vec::new();
+        if let Some(width) = self.width {
+            params.push(("w", width.to_string()));
+        }
+        if let Some(height) = self.height {
+            params.push(("h", height.to_string()));
+        }
+        if let Some(ref fit) = self.fit {
+            params.push(("fit", fit.to_string()));
+        }
+        if let Some(quality) = self.quality {
+            params.push(("q", quality.to_string()));
+        }
+        if !params.is_empty() {
+            url.query_pairs_mut().extend_pairs(params);
+        }
+
+        Ok(url.into())
+    }
+
+    fn validate(&self) -> Result<(), UrlError> {
+        if let Some(width) = self.width.filter(|w| *w == 0) {
+            return Err(UrlError::Width(width));
+        }
+        if let Some(height) = self.height.filter(|h| *h == 0) {
+            return Err(UrlError::Height(height));
+        }
+        if let Some(quality) = self.quality.filter(|q| !q.is_valid()) {
+            return Err(UrlError::Quality(quality.get()));
+        }
+        Ok(())
+    }
+}
+
+fn parse(url: &str) -> Result<Url, UrlError> {
+    Url::parse(url).map_err(|_| UrlError::InvalidUrl(url.to_owned()))
+}
+
+/// Replaces (or appends) the extension of the last path segment.
+fn with_extension(path: &str, extension: &str) -> String {
+    let (dir, file) = path.rsplit_once('/').unwrap_or(("", path));
+    let stem = match file.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => file,
+    };
+    format!("{dir}/{stem}.{extension}")
+}
@@ -0,0 +1,467 @@
+//! High-level client facade over the free functions in [`crate::apis`].
+//!
+//! [`ImgSrcClient`] owns a [`Configuration`] and hands out lightweight,
+//! borrowed handles grouped by resource, so callers inject one object
+//! instead of threading `&Configuration` through every call:
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = img_src::ImgSrcClient::new("imgsrc_...");
+//! let images = client.images().list(Some(20), None, None).await?;
+//! let presets = client.presets().list().await?;
+//! let usage = client.usage().get().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use futures_util::Stream;
+
+use crate::apis::configuration::{Configuration, CredentialProvider, Secret};
+use crate::apis::{
+    api_keys_api, health_api, images_api, presets_api, settings_api, usage_api, Error,
+};
+use crate::bulk::{
+    self, BulkDeleteOptions, DeleteReport, DeleteTarget, UploadDirOptions, UploadReport,
+};
+use crate::dedup::{self, HashIndex, PrecheckedUpload};
+use crate::folders::{self, DeleteFolderError, DeleteFolderReport, FolderNode, FolderTreeOptions};
+use crate::models;
+use crate::pagination::{self, ListImagesOptions};
+use crate::preset_sync::{self, PresetConfig, PresetPlan, PresetSyncOptions, PresetSyncReport};
+use crate::sync::{self, Manifest, SyncError, SyncOptions, SyncReport};
+
+/// Cloneable img-src client. Clones share the same [`Configuration`].
+#[derive(Debug, Clone)]
+pub struct ImgSrcClient {
+    configuration: Arc<Configuration>,
+}
+
+impl ImgSrcClient {
+    /// Creates a client for the default server authenticated with `api_key`.
+    pub fn new(api_key: impl Into<Secret>) -> ImgSrcClient {
+        let mut configuration = Configuration::new();
+        configuration.bearer_access_token = Some(api_key.into());
+        ImgSrcClient::from_configuration(configuration)
+    }
+
+    /// Creates a client for the default server that asks `provider` for a
+    /// token on every request.
+    pub fn with_credential_provider(provider: impl CredentialProvider + 'static) -> ImgSrcClient {
+        let mut configuration = Configuration::new();
+        configuration.credential_provider = Some(Arc::new(provider));
+        ImgSrcClient::from_configuration(configuration)
+    }
+
+    /// Creates a client from an existing configuration.
+    pub fn from_configuration(configuration: Configuration) -> ImgSrcClient {
+        ImgSrcClient {
+            configuration: Arc::new(configuration),
+        }
+    }
+
+    /// The configuration used for every request made through this client.
+    pub fn configuration(&self) -> &Configuration {
+        &self.configuration
+    }
+
+    /// API key management operations.
+    pub fn api_keys(&self) -> ApiKeysClient<'_> {
+        ApiKeysClient {
+            configuration: &self.configuration,
+        }
+    }
+
+    /// Service health check.
+    pub fn health(&self) -> HealthClient<'_> {
+        HealthClient {
+            configuration: &self.configuration,
+        }
+    }
+
+    /// Image upload, listing, search, deletion and signed URL operations.
+    pub fn images(&self) -> ImagesClient<'_> {
+        ImagesClient {
+            configuration: &self.configuration,
+        }
+    }
+
+    /// Transformation preset operations.
+    pub fn presets(&self) -> PresetsClient<'_> {
+        PresetsClient {
+            configuration: &self.configuration,
+        }
+    }
+
+    /// User settings operations.
+    pub fn settings(&self) -> SettingsClient<'_> {
+        SettingsClient {
+            configuration: &self.configuration,
+        }
+    }
+
+    /// Usage statistics operations.
+    pub fn usage(&self) -> UsageClient<'_> {
+        UsageClient {
+            configuration: &self.configuration,
+        }
+    }
+}
+
+impl From<Configuration> for ImgSrcClient {
+    fn from(configuration: Configuration) -> Self {
+        ImgSrcClient::from_configuration(configuration)
+    }
+}
+
+/// Handle for the operations in [`api_keys_api`].
+#[derive(Debug, Clone, Copy)]
+pub struct ApiKeysClient<'a> {
+    configuration: &'a Configuration,
+}
+
+impl ApiKeysClient<'_> {
+    /// See [`api_keys_api::list_api_keys`].
+    pub async fn list(
+        &self,
+    ) -> Result<models::ApiKeyListResponse, Error<api_keys_api::ListApiKeysError>> {
+        api_keys_api::list_api_keys(self.configuration).await
+    }
+
+    /// See [`api_keys_api::create_api_key`].
+    pub async fn create(
+        &self,
+        create_api_key_request: Option<models::CreateApiKeyRequest>,
+    ) -> Result<models::CreateApiKeyResponse, Error<api_keys_api::CreateApiKeyError>> {
+        api_keys_api::create_api_key(self.configuration, create_api_key_request).await
+    }
+
+    /// See [`api_keys_api::delete_api_key`].
+    pub async fn delete(
+        &self,
+        id: &str,
+    ) -> Result<models::DeleteApiKeyResponse, Error<api_keys_api::DeleteApiKeyError>> {
+        api_keys_api::delete_api_key(self.configuration, id).await
+    }
+}
+
+/// Handle for the operations in [`health_api`].
+#[derive(Debug, Clone, Copy)]
+pub struct HealthClient<'a> {
+    configuration: &'a Configuration,
+}
+
+impl HealthClient<'_> {
+    /// See [`health_api::health_check`].
+    pub async fn check(
+        &self,
+    ) -> Result<models::HealthResponse, Error<health_api::HealthCheckError>> {
+        health_api::health_check(self.configuration).await
+    }
+
+    /// See [`health_api::ReadinessCheck::is_ready`].
+    pub async fn is_ready(&self, readiness: &health_api::ReadinessCheck) -> bool {
+        readiness.is_ready(self.configuration).await
+    }
+}
+
+/// Handle for the operations in [`images_api`].
+#[derive(Debug, Clone, Copy)]
+pub struct ImagesClient<'a> {
+    configuration: &'a Configuration,
+}
+
+impl ImagesClient<'_> {
+    /// See [`images_api::upload_image`].
+    pub async fn upload(
+        &self,
+        file: Option<std::path::PathBuf>,
+        target_path: Option<&str>,
+        visibility: Option<models::Visibility>,
+    ) -> Result<models::UploadResponse, Error<images_api::UploadImageError>> {
+        images_api::upload_image(self.configuration, file, target_path, visibility).await
+    }
+
+    /// See [`images_api::upload_image_bytes`].
+    pub async fn upload_bytes(
+        &self,
+        data: impl Into<bytes::Bytes>,
+        file_name: &str,
+        content_type: &str,
+        target_path: Option<&str>,
+        visibility: Option<models::Visibility>,
+    ) -> Result<models::UploadResponse, Error<images_api::UploadImageError>> {
+        images_api::upload_image_bytes(
+            self.configuration,
+            data,
+            file_name,
+            content_type,
+            target_path,
+            visibility,
+        )
+        .await
+    }
+
+    /// See [`images_api::upload_image_reader`].
+    pub async fn upload_reader<R>(
+        &self,
+        reader: R,
+        file_name: &str,
+        content_type: &str,
+        target_path: Option<&str>,
+        visibility: Option<models::Visibility>,
+    ) -> Result<models::UploadResponse, Error<images_api::UploadImageError>>
+    where
+        R: tokio::io::AsyncRead + Send + 'static,
+    {
+        images_api::upload_image_reader(
+            self.configuration,
+            reader,
+            file_name,
+            content_type,
+            target_path,
+            visibility,
+        )
+        .await
+    }
+
+    /// See [`dedup::upload_image_if_new`].
+    pub async fn upload_if_new(
+        &self,
+        index: &HashIndex,
+        file: std::path::PathBuf,
+        target_path: Option<&str>,
+        visibility: Option<models::Visibility>,
+    ) -> Result<PrecheckedUpload, Error<images_api::UploadImageError>> {
+        dedup::upload_image_if_new(self.configuration, index, file, target_path, visibility).await
+    }
+
+    /// See [`bulk::upload_dir`].
+    pub async fn upload_dir(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+        options: &UploadDirOptions,
+    ) -> std::io::Result<UploadReport> {
+        bulk::upload_dir(self.configuration, dir, options).await
+    }
+
+    /// See [`sync::sync_dir`].
+    pub async fn sync_dir(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+        manifest: &mut Manifest,
+        options: &SyncOptions,
+    ) -> Result<SyncReport, SyncError> {
+        sync::sync_dir(self.configuration, dir, manifest, options).await
+    }
+
+    /// See [`images_api::list_images`].
+    pub async fn list(
+        &self,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        path: Option<&str>,
+    ) -> Result<models::ImageListResponse, Error<images_api::ListImagesError>> {
+        images_api::list_images(self.configuration, limit, offset, path).await
+    }
+
+    /// Streams every image under `path` with the default page size and
+    /// prefetch. See [`pagination::list_images_stream`].
+    pub fn list_stream(
+        &self,
+        path: Option<&str>,
+    ) -> impl Stream<Item = Result<models::ImageListItem, Error<images_api::ListImagesError>>>
+           + Send
+           + 'static {
+        pagination::list_images_stream(self.configuration, path, ListImagesOptions::default())
+    }
+
+    /// See [`pagination::list_images_stream`].
+    pub fn list_stream_with(
+        &self,
+        path: Option<&str>,
+        options: ListImagesOptions,
+    ) -> impl Stream<Item = Result<models::ImageListItem, Error<images_api::ListImagesError>>>
+           + Send
+           + 'static {
+        pagination::list_images_stream(self.configuration, path, options)
+    }
+
+    /// See [`folders::folder_tree`].
+    pub async fn folder_tree(
+        &self,
+        root: Option<&str>,
+        options: FolderTreeOptions,
+    ) -> Result<FolderNode, Error<images_api::ListImagesError>> {
+        folders::folder_tree(self.configuration, root, options).await
+    }
+
+    /// See [`images_api::search_images`].
+    pub async fn search(
+        &self,
+        q: &str,
+        limit: Option<i32>,
+    ) -> Result<models::SearchResponse, Error<images_api::SearchImagesError>> {
+        images_api::search_images(self.configuration, q, limit).await
+    }
+
+    /// See [`images_api::get_image`].
+    pub async fn get(
+        &self,
+        id: &str,
+    ) -> Result<models::MetadataResponse, Error<images_api::GetImageError>> {
+        images_api::get_image(self.configuration, id).await
+    }
+
+    /// See [`images_api::delete_image`].
+    pub async fn delete(
+        &self,
+        id: &str,
+    ) -> Result<models::DeleteResponse, Error<images_api::DeleteImageError>> {
+        images_api::delete_image(self.configuration, id).await
+    }
+
+    /// See [`images_api::delete_image_path`].
+    pub async fn delete_path(
+        &self,
+        username: &str,
+        filepath: &str,
+    ) -> Result<models::PathDeleteResponse, Error<images_api::DeleteImagePathError>> {
+        images_api::delete_image_path(self.configuration, username, filepath).await
+    }
+
+    /// See [`bulk::delete_many`].
+    pub async fn delete_many(
+        &self,
+        targets: impl IntoIterator<Item = DeleteTarget>,
+        options: &BulkDeleteOptions,
+    ) -> Result<DeleteReport, Error<settings_api::GetSettingsError>> {
+        bulk::delete_many(self.configuration, targets, options).await
+    }
+
+    /// See [`folders::delete_folder`].
+    pub async fn delete_folder(
+        &self,
+        prefix: &str,
+        options: &BulkDeleteOptions,
+    ) -> Result<DeleteFolderReport, DeleteFolderError> {
+        folders::delete_folder(self.configuration, prefix, options).await
+    }
+
+    /// See [`images_api::create_signed_url`].
+    pub async fn create_signed_url(
+        &self,
+        id: &str,
+        create_signed_url_request: Option<models::CreateSignedUrlRequest>,
+    ) -> Result<models::SignedUrlResponse, Error<images_api::CreateSignedUrlError>> {
+        images_api::create_signed_url(self.configuration, id, create_signed_url_request).await
+    }
+
+    /// See [`images_api::update_visibility`].
+    pub async fn update_visibility(
+        &self,
+        id: &str,
+        update_visibility_request: models::UpdateVisibilityRequest,
+    ) -> Result<models::UpdateVisibilityResponse, Error<images_api::UpdateVisibilityError>> {
+        images_api::update_visibility(self.configuration, id, update_visibility_request).await
+    }
+}
+
+/// Handle for the operations in [`presets_api`].
+#[derive(Debug, Clone, Copy)]
+pub struct PresetsClient<'a> {
+    configuration: &'a Configuration,
+}
+
+impl PresetsClient<'_> {
+    /// See [`presets_api::list_presets`].
+    pub async fn list(
+        &self,
+    ) -> Result<models::ListPresetsResponse, Error<presets_api::ListPresetsError>> {
+        presets_api::list_presets(self.configuration).await
+    }
+
+    /// See [`presets_api::create_preset`].
+    pub async fn create(
+        &self,
+        create_preset_request: Option<models::CreatePresetRequest>,
+    ) -> Result<models::Preset, Error<presets_api::CreatePresetError>> {
+        presets_api::create_preset(self.configuration, create_preset_request).await
+    }
+
+    /// See [`presets_api::get_preset`].
+    pub async fn get(
+        &self,
+        id: &str,
+    ) -> Result<models::Preset, Error<presets_api::GetPresetError>> {
+        presets_api::get_preset(self.configuration, id).await
+    }
+
+    /// See [`presets_api::update_preset`].
+    pub async fn update(
+        &self,
+        id: &str,
+        update_preset_request: Option<models::UpdatePresetRequest>,
+    ) -> Result<models::Preset, Error<presets_api::UpdatePresetError>> {
+        presets_api::update_preset(self.configuration, id, update_preset_request).await
+    }
+
+    /// See [`presets_api::delete_preset`].
+    pub async fn delete(
+        &self,
+        name: &str,
+    ) -> Result<models::DeletePresetResponse, Error<presets_api::DeletePresetError>> {
+        presets_api::delete_preset(self.configuration, name).await
+    }
+
+    /// See [`preset_sync::plan_presets`].
+    pub async fn plan(
+        &self,
+        desired: &PresetConfig,
+        options: &PresetSyncOptions,
+    ) -> Result<PresetPlan, Error<presets_api::ListPresetsError>> {
+        preset_sync::plan_presets(self.configuration, desired, options).await
+    }
+
+    /// See [`preset_sync::apply_presets`].
+    pub async fn apply(&self, plan: PresetPlan) -> PresetSyncReport {
+        preset_sync::apply_presets(self.configuration, plan).await
+    }
+}
+
+/// Handle for the operations in [`settings_api`].
+#[derive(Debug, Clone, Copy)]
+pub struct SettingsClient<'a> {
+    configuration: &'a Configuration,
+}
+
+impl SettingsClient<'_> {
+    /// See [`settings_api::get_settings`].
+    pub async fn get(
+        &self,
+    ) -> Result<models::SettingsResponse, Error<settings_api::GetSettingsError>> {
+        settings_api::get_settings(self.configuration).await
+    }
+
+    /// See [`settings_api::update_settings`].
+    pub async fn update(
+        &self,
+        update_settings_request: Option<models::UpdateSettingsRequest>,
+    ) -> Result<models::SettingsUpdateResponse, Error<settings_api::UpdateSettingsError>> {
+        settings_api::update_settings(self.configuration, update_settings_request).await
+    }
+}
+
+/// Handle for the operations in [`usage_api`].
+#[derive(Debug, Clone, Copy)]
+pub struct UsageClient<'a> {
+    configuration: &'a Configuration,
+}
+
+impl UsageClient<'_> {
+    /// See [`usage_api::get_usage`].
+    pub async fn get(&self) -> Result<models::UsageResponse, Error<usage_api::GetUsageError>> {
+        usage_api::get_usage(self.configuration).await
+    }
+}
@@ -0,0 +1,234 @@
+//! Local SHA-256 pre-check for uploads.
+//!
+//! img-src deduplicates on the SHA-256 of the content, and an image's ID is
+//! the first 16 hex characters of that hash. Hashing a file locally therefore
+//! tells whether the account already stores it, before any bytes are sent.
+//! [`HashIndex`] keeps the known images and the paths they are published
+//! under; [`upload_image_if_new`] consults it and skips uploads that would
+//! not change anything.
+//!
+//! The API has no call to attach an additional path to stored content, so
+//! content that exists under a different path is still uploaded; the server
+//! then only records the new path.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt;
+
+use crate::apis::configuration::Configuration;
+use crate::apis::images_api::{self, ListImagesError, UploadImageError};
+use crate::apis::Error;
+use crate::folders::{self, FolderTreeOptions};
+use crate::models;
+
+/// Length of an image ID, in hex characters of the content hash.
+pub const IMAGE_ID_LEN: usize = 16;
+
+/// Hex-encoded SHA-256 of the file at `path`, read in chunks.
+pub async fn sha256_file(path: impl AsRef<Path>) -> io::Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex(&hasher.finalize()))
+}
+
+/// Hex-encoded SHA-256 of `data`.
+pub fn sha256_bytes(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+/// The image ID img-src assigns to content with the given SHA-256.
+pub fn image_id_for_hash(hash: &str) -> &str {
+    hash.get(..IMAGE_ID_LEN).unwrap_or(hash)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct IndexEntry {
+    /// Full content hash, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+    #[serde(default)]
+    paths: BTreeSet<String>,
+}
+
+/// Images known to exist on the account, keyed by image ID.
+///
+/// The index is safe to share between concurrent uploads and can be saved to
+/// and loaded from a JSON file between runs.
+#[derive(Debug, Default)]
+pub struct HashIndex {
+    entries: Mutex<BTreeMap<String, IndexEntry>>,
+    /// Account username, which may lead the paths returned by the API.
+    username: Option<String>,
+}
+
+impl HashIndex {
+    /// Creates an empty index.
+    pub fn new() -> HashIndex {
+        HashIndex::default()
+    }
+
+    /// Sets the account username, so `username/photos/cat.png` and
+    /// `photos/cat.png` are treated as the same path.
+    pub fn with_username(mut self, username: impl Into<String>) -> HashIndex {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Builds an index of every image at or below `root`.
+    pub async fn fetch(
+        configuration: &Configuration,
+        root: Option<&str>,
+    ) -> Result<HashIndex, Error<ListImagesError>> {
+        let tree = folders::folder_tree(configuration, root, FolderTreeOptions::default()).await?;
+        let index = HashIndex::new();
+        for folder in tree.walk() {
+            for image in &folder.images {
+                index.record_listed(image);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Loads an index saved with [`HashIndex::save`].
+    pub async fn load(path: impl AsRef<Path>) -> io::Result<HashIndex> {
+        let data = tokio::fs::read(path).await?;
+        let entries = serde_json::from_slice(&data).map_err(io::Error::other)?;
+        Ok(HashIndex {
+            entries: Mutex::new(entries),
+            username: None,
+        })
+    }
+
+    /// Writes the index as JSON to `path`.
+    pub async fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let data = serde_json::to_vec_pretty(&*self.lock()).map_err(io::Error::other)?;
+        tokio::fs::write(path, data).await
+    }
+
+    /// Number of images in the index.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the index holds no images.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Whether content with this SHA-256 is stored on the account.
+    pub fn contains_hash(&self, hash: &str) -> bool {
+        match self.lock().get(image_id_for_hash(hash)) {
+            Some(IndexEntry {
+                hash: Some(known), ..
+            }) => known.eq_ignore_ascii_case(hash),
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Whether content with this SHA-256 is published at `path`
+    /// (e.g. `photos/2024/cat.png`). A leading
+    /// [username](HashIndex::with_username) segment is ignored on both sides.
+    pub fn contains_path(&self, hash: &str, path: &str) -> bool {
+        if !self.contains_hash(hash) {
+            return false;
+        }
+        let path = self.account_relative(path);
+        self.lock()
+            .get(image_id_for_hash(hash))
+            .is_some_and(|entry| entry.paths.iter().any(|p| self.account_relative(p) == path))
+    }
+
+    /// `path` without surrounding slashes and the leading username segment.
+    fn account_relative<'a>(&self, path: &'a str) -> &'a str {
+        let path = path.trim_matches('/');
+        self.username
+            .as_deref()
+            .and_then(|username| path.strip_prefix(username)?.strip_prefix('/'))
+            .unwrap_or(path)
+    }
+
+    /// Adds a known image with the paths it is published under.
+    pub fn insert<I, S>(&self, id: &str, hash: Option<&str>, paths: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut entries = self.lock();
+        let entry = entries.entry(id.to_owned()).or_default();
+        if let Some(hash) = hash {
+            entry.hash = Some(hash.to_ascii_lowercase());
+        }
+        entry.paths.extend(paths.into_iter().map(Into::into));
+    }
+
+    /// Records the result of an upload.
+    pub fn record_upload(&self, upload: &models::UploadResponse) {
+        self.insert(&upload.id, Some(&upload.hash), upload.paths.iter().cloned());
+    }
+
+    /// Records an image from a listing.
+    pub fn record_listed(&self, image: &models::ImageListItem) {
+        self.insert(&image.id, None, image.paths.iter().cloned());
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, IndexEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Result of [`upload_image_if_new`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrecheckedUpload {
+    /// The content is already published at the target path; nothing was sent.
+    AlreadyUploaded { id: String, hash: String },
+    /// The file was uploaded.
+    Uploaded(Box<models::UploadResponse>),
+}
+
+/// Uploads `file` unless `index` shows its content is already published
+/// under `target_path`, recording successful uploads in the index.
+pub async fn upload_image_if_new(
+    configuration: &Configuration,
+    index: &HashIndex,
+    file: PathBuf,
+    target_path: Option<&str>,
+    visibility: Option<models::Visibility>,
+) -> Result<PrecheckedUpload, Error<UploadImageError>> {
+    let hash = sha256_file(&file).await?;
+    let file_name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let published_path = match target_path.map(|t| t.trim_matches('/')) {
+        Some(target) if !target.is_empty() => format!("{target}/{file_name}"),
+        _ => file_name,
+    };
+    if index.contains_path(&hash, &published_path) {
+        return Ok(PrecheckedUpload::AlreadyUploaded {
+            id: image_id_for_hash(&hash).to_owned(),
+            hash,
+        });
+    }
+    let upload =
+        images_api::upload_image(configuration, Some(file), target_path, visibility).await?;
+    index.record_upload(&upload);
+    Ok(PrecheckedUpload::Uploaded(Box::new(upload)))
+}
@@ -0,0 +1,238 @@
+//! Crate-wide error type.
+//!
+//! Every endpoint returns [`apis::Error<T>`](crate::apis::Error), generic over
+//! the endpoint's typed error enum. [`ImgSrcError`] drops that parameter so
+//! results of different endpoints, and of the helpers built on top of them,
+//! can be combined with `?`:
+//!
+//! ```no_run
+//! # async fn run(config: &img_src::apis::configuration::Configuration) -> Result<(), img_src::ImgSrcError> {
+//! use img_src::apis::images_api;
+//!
+//! let upload = images_api::upload_image(config, Some("cat.png".into()), None, None).await?;
+//! let signed = images_api::create_signed_url(config, &upload.id, None).await?;
+//! println!("{}", signed.signed_url);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::error;
+use std::fmt;
+use std::io;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use crate::apis::auth::AuthError;
+use crate::apis::configuration::RetryPolicy;
+use crate::apis::{Error, RateLimitInfo};
+use crate::cdn::UrlError;
+use crate::folders::DeleteFolderError;
+use crate::models;
+use crate::models::preset_params::PresetParamsError;
+use crate::preset_sync::PresetConfigError;
+use crate::sync::SyncError;
+
+/// Error of any operation in this crate.
+#[derive(Debug)]
+pub enum ImgSrcError {
+    /// The API answered with an error status.
+    Api {
+        status: StatusCode,
+        /// Headers of the error response.
+        headers: HeaderMap,
+        /// The parsed error body, if it was a standard error response.
+        detail: Option<models::ErrorDetail>,
+        /// The raw response body.
+        content: String,
+    },
+    /// Sending the request or reading the response failed.
+    Reqwest(reqwest::Error),
+    /// A response body could not be parsed.
+    Serde(serde_json::Error),
+    /// Reading or writing a local file failed.
+    Io(io::Error),
+    /// The request could not be authenticated, e.g. no credential is set.
+    Auth(AuthError),
+    /// An argument was rejected before any request was sent.
+    Invalid(Box<dyn error::Error + Send + Sync>),
+}
+
+impl ImgSrcError {
+    /// HTTP status of an API error.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ImgSrcError::Api { status, .. } => Some(*status),
+            ImgSrcError::Reqwest(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    /// Error details sent by the API.
+    pub fn detail(&self) -> Option<&models::ErrorDetail> {
+        match self {
+            ImgSrcError::Api { detail, .. } => detail.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// API error code, e.g. `NOT_FOUND` or `VALIDATION_ERROR`.
+    pub fn code(&self) -> Option<&str> {
+        self.detail().map(|d| d.code.as_str())
+    }
+
+    /// Rate-limit state sent with an API error, e.g. on a `429`.
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        match self {
+            ImgSrcError::Api { headers, .. } => RateLimitInfo::from_headers(headers),
+            _ => None,
+        }
+    }
+
+    /// Identifier of the failed request to quote in support requests.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            ImgSrcError::Api { headers, .. } => crate::apis::response::request_id(headers),
+            _ => None,
+        }
+    }
+
+    /// Human-readable message sent by the API.
+    pub fn message(&self) -> Option<&str> {
+        self.detail().map(|d| d.message.as_str())
+    }
+
+    /// Request path reported by the API.
+    pub fn path(&self) -> Option<&str> {
+        self.detail().and_then(|d| d.path.as_deref())
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        self.status() == Some(StatusCode::CONFLICT)
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        self.status() == Some(StatusCode::UNAUTHORIZED)
+    }
+
+    /// Whether sending the request again may succeed: connection failures,
+    /// timeouts and the statuses retried by [`RetryPolicy::default`].
+    pub fn is_retryable(&self) -> bool {
+        if let ImgSrcError::Reqwest(e) = self {
+            if e.is_connect() || e.is_timeout() {
+                return true;
+            }
+        }
+        self.status()
+            .is_some_and(|status| RetryPolicy::default().retry_statuses.contains(&status))
+    }
+
+    fn invalid(e: impl error::Error + Send + Sync + 'static) -> ImgSrcError {
+        ImgSrcError::Invalid(Box::new(e))
+    }
+}
+
+impl fmt::Display for ImgSrcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImgSrcError::Api {
+                status,
+                detail: Some(detail),
+                ..
+            } => write!(f, "API error {status}: {}: {}", detail.code, detail.message),
+            ImgSrcError::Api { status, .. } => write!(f, "API error {status}"),
+            ImgSrcError::Reqwest(e) => write!(f, "request failed: {e}"),
+            ImgSrcError::Serde(e) => write!(f, "invalid response body: {e}"),
+            ImgSrcError::Io(e) => write!(f, "IO error: {e}"),
+            ImgSrcError::Auth(e) => write!(f, "authentication failed: {e}"),
+            ImgSrcError::Invalid(e) => write!(f, "invalid argument: {e}"),
+        }
+    }
+}
+
+impl error::Error for ImgSrcError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ImgSrcError::Api { .. } => None,
+            ImgSrcError::Reqwest(e) => Some(e),
+            ImgSrcError::Serde(e) => Some(e),
+            ImgSrcError::Io(e) => Some(e),
+            ImgSrcError::Auth(e) => Some(e),
+            ImgSrcError::Invalid(e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl<T> From<Error<T>> for ImgSrcError {
+    fn from(e: Error<T>) -> Self {
+        match e {
+            Error::Reqwest(e) => ImgSrcError::Reqwest(e),
+            Error::Serde(e) => ImgSrcError::Serde(e),
+            Error::Io(e) => ImgSrcError::Io(e),
+            Error::Auth(e) => ImgSrcError::Auth(e),
+            Error::ResponseError(response) => ImgSrcError::Api {
+                status: response.status,
+                headers: response.headers,
+                detail: serde_json::from_str::<models::ErrorResponse>(&response.content)
+                    .ok()
+                    .map(|body| *body.error),
+                content: response.content,
+            },
+        }
+    }
+}
+
+impl From<io::Error> for ImgSrcError {
+    fn from(e: io::Error) -> Self {
+        ImgSrcError::Io(e)
+    }
+}
+
+impl From<UrlError> for ImgSrcError {
+    fn from(e: UrlError) -> Self {
+        ImgSrcError::invalid(e)
+    }
+}
+
+impl From<PresetParamsError> for ImgSrcError {
+    fn from(e: PresetParamsError) -> Self {
+        ImgSrcError::invalid(e)
+    }
+}
+
+impl From<SyncError> for ImgSrcError {
+    fn from(e: SyncError) -> Self {
+        match e {
+            SyncError::Io(e) => e.into(),
+            SyncError::ListImages(e) => e.into(),
+            SyncError::GetSettings(e) => e.into(),
+        }
+    }
+}
+
+impl From<DeleteFolderError> for ImgSrcError {
+    fn from(e: DeleteFolderError) -> Self {
+        match e {
+            DeleteFolderError::GetSettings(e) => e.into(),
+            DeleteFolderError::ListImages(e) => e.into(),
+            e @ DeleteFolderError::EmptyPrefix => ImgSrcError::invalid(e),
+        }
+    }
+}
+
+impl From<PresetConfigError> for ImgSrcError {
+    fn from(e: PresetConfigError) -> Self {
+        match e {
+            PresetConfigError::Io(e) => e.into(),
+            e => ImgSrcError::invalid(e),
+        }
+    }
+}
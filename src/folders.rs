@@ -0,0 +1,397 @@
+//! Recursive folder tree traversal.
+//!
+//! [`images_api::list_images`] only reports the direct subfolders of a path.
+//! [`folder_tree`] descends into each of them and returns the whole hierarchy
+//! below a root, with the images of every folder and aggregate counts and
+//! sizes for rendering a folder browser. [`delete_folder`] removes every path
+//! below a folder.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use tokio::sync::Semaphore;
+
+use crate::apis::configuration::Configuration;
+use crate::apis::images_api::{self, ListImagesError};
+use crate::apis::settings_api::{self, GetSettingsError};
+use crate::apis::Error;
+use crate::bulk::{self, BulkDeleteOptions, DeleteOutcome, DeleteResult, DeleteTarget};
+use crate::models;
+
+/// How far and in what page size [`folder_tree`] walks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FolderTreeOptions {
+    /// Images requested per page when listing a folder.
+    pub page_size: i32,
+    /// Levels below the root to descend into; `None` walks the whole tree.
+    /// Folders past this depth are returned without images or children.
+    pub max_depth: Option<usize>,
+    /// Maximum number of listing requests in flight across the whole walk.
+    pub concurrency: usize,
+}
+
+impl Default for FolderTreeOptions {
+    fn default() -> Self {
+        FolderTreeOptions {
+            page_size: 100,
+            max_depth: None,
+            concurrency: 4,
+        }
+    }
+}
+
+/// A folder together with its images and subfolders.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FolderNode {
+    /// Full path without leading or trailing slashes; empty for the root.
+    pub path: String,
+    /// Last path segment; empty for the root.
+    pub name: String,
+    /// Image count reported by the parent listing, including subfolders.
+    /// `None` for the root of the walk.
+    pub reported_image_count: Option<i32>,
+    /// Images directly in this folder.
+    pub images: Vec<models::ImageListItem>,
+    /// Direct subfolders, in listing order.
+    pub folders: Vec<FolderNode>,
+    /// Whether the subtree was listed; `false` past
+    /// [`FolderTreeOptions::max_depth`].
+    pub expanded: bool,
+}
+
+impl FolderNode {
+    /// Number of images in this folder and all listed subfolders.
+    pub fn image_count(&self) -> usize {
+        self.images.len()
+            + self
+                .folders
+                .iter()
+                .map(FolderNode::image_count)
+                .sum::<usize>()
+    }
+
+    /// Bytes used by images directly in this folder.
+    pub fn own_size(&self) -> i64 {
+        self.images.iter().map(|image| image.size).sum()
+    }
+
+    /// Bytes used by this folder and all listed subfolders.
+    pub fn total_size(&self) -> i64 {
+        self.own_size() + self.folders.iter().map(FolderNode::total_size).sum::<i64>()
+    }
+
+    /// Finds the descendant (or this node) at `path`.
+    pub fn find(&self, path: &str) -> Option<&FolderNode> {
+        let path = normalize(path);
+        if path == self.path {
+            return Some(self);
+        }
+        self.folders
+            .iter()
+            .filter(|child| path.starts_with(&child.path))
+            .find_map(|child| child.find(&path))
+    }
+
+    /// Iterates over this node and its descendants, depth first.
+    pub fn walk(&self) -> impl Iterator<Item = &FolderNode> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.folders.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// Lists `root` (the top level when `None`) and every folder below it.
+pub async fn folder_tree(
+    configuration: &Configuration,
+    root: Option<&str>,
+    options: FolderTreeOptions,
+) -> Result<FolderNode, Error<ListImagesError>> {
+    let path = normalize(root.unwrap_or_default());
+    let name = path.rsplit('/').next().unwrap_or_default().to_owned();
+    let node = FolderNode {
+        path,
+        name,
+        ..FolderNode::default()
+    };
+    let permits = Semaphore::new(options.concurrency.max(1));
+    expand(configuration, &permits, node, 0, options).await
+}
+
+fn expand<'a>(
+    configuration: &'a Configuration,
+    permits: &'a Semaphore,
+    mut node: FolderNode,
+    depth: usize,
+    options: FolderTreeOptions,
+) -> Pin<Box<dyn Future<Output = Result<FolderNode, Error<ListImagesError>>> + Send + 'a>> {
+    Box::pin(async move {
+        if options.max_depth.is_some_and(|max| depth > max) {
+            return Ok(node);
+        }
+        // The permit is released before descending, so parents waiting on
+        // their children never starve them.
+        let (images, folders) = {
+            let _permit = permits.acquire().await.expect("semaphore is never closed");
+            list_folder(configuration, &node.path, options.page_size).await?
+        };
+        node.images = images;
+        node.expanded = true;
+        let children = folders.into_iter().map(|folder| {
+            let child = FolderNode {
+                path: join(&node.path, &folder.name),
+                name: normalize(&folder.name),
+                reported_image_count: Some(folder.image_count),
+                ..FolderNode::default()
+            };
+            expand(configuration, permits, child, depth + 1, options)
+        });
+        node.folders = stream::iter(children)
+            .buffered(options.concurrency.max(1))
+            .try_collect()
+            .await?;
+        Ok(node)
+    })
+}
+
+/// Reads every page of one folder, deduplicating images and subfolders.
+async fn list_folder(
+    configuration: &Configuration,
+    path: &str,
+    page_size: i32,
+) -> Result<(Vec<models::ImageListItem>, Vec<models::FolderItem>), Error<ListImagesError>> {
+    let page_size = page_size.max(1);
+    let filter = (!path.is_empty()).then_some(path);
+    let mut images = Vec::new();
+    let mut folders = Vec::new();
+    let mut seen_images = HashSet::new();
+    let mut seen_folders = HashSet::new();
+    let mut offset = 0;
+    loop {
+        let page =
+            images_api::list_images(configuration, Some(page_size), Some(offset), filter).await?;
+        let done = !page.has_more || page.images.is_empty();
+        images.extend(
+            page.images
+                .into_iter()
+                .filter(|image| seen_images.insert(image.id.clone())),
+        );
+        folders.extend(
+            page.folders
+                .into_iter()
+                .filter(|folder| seen_folders.insert(folder.name.clone())),
+        );
+        if done {
+            return Ok((images, folders));
+        }
+        offset += page_size;
+    }
+}
+
+/// What [`delete_folder`] did to one image below the folder.
+#[derive(Debug)]
+pub struct FolderImageDeletion {
+    pub id: String,
+    /// Deletions of the image's paths inside the folder.
+    pub paths: Vec<DeleteResult>,
+    /// Paths of the image outside the folder, which are left untouched.
+    pub outside_paths: Vec<String>,
+}
+
+impl FolderImageDeletion {
+    /// Whether the image itself was removed, or would be in a dry run.
+    pub fn destroyed(&self) -> bool {
+        if self.is_planned() {
+            return self.outside_paths.is_empty();
+        }
+        self.paths.iter().any(|r| match &r.outcome {
+            DeleteOutcome::PathDeleted(resp) => resp.image_deleted,
+            _ => false,
+        })
+    }
+
+    /// Whether only the folder's paths were removed and the image is still
+    /// published elsewhere, or would be in a dry run.
+    pub fn unlinked(&self) -> bool {
+        !self.destroyed() && !self.has_failures()
+    }
+
+    /// Whether deleting any of the paths failed.
+    pub fn has_failures(&self) -> bool {
+        self.paths
+            .iter()
+            .any(|r| matches!(r.outcome, DeleteOutcome::Failed(_)))
+    }
+
+    fn is_planned(&self) -> bool {
+        self.paths
+            .iter()
+            .all(|r| matches!(r.outcome, DeleteOutcome::Planned))
+    }
+}
+
+/// Per-image results of [`delete_folder`].
+#[derive(Debug, Default)]
+pub struct DeleteFolderReport {
+    /// The folder, without leading or trailing slashes.
+    pub prefix: String,
+    /// Every image found below the folder, in listing order.
+    pub images: Vec<FolderImageDeletion>,
+    /// IDs of images listed below the folder without any path. Nothing is
+    /// deleted for them, so the folder may still hold them afterwards.
+    pub skipped: Vec<String>,
+}
+
+impl DeleteFolderReport {
+    /// Images removed entirely.
+    pub fn destroyed(&self) -> impl Iterator<Item = &FolderImageDeletion> {
+        self.images.iter().filter(|image| image.destroyed())
+    }
+
+    /// Images that keep paths outside the folder.
+    pub fn unlinked(&self) -> impl Iterator<Item = &FolderImageDeletion> {
+        self.images.iter().filter(|image| image.unlinked())
+    }
+
+    /// Images with at least one failed path deletion.
+    pub fn failed(&self) -> impl Iterator<Item = &FolderImageDeletion> {
+        self.images.iter().filter(|image| image.has_failures())
+    }
+
+    /// Whether no deletion failed.
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+/// Error returned by [`delete_folder`] before any path is deleted.
+#[derive(Debug)]
+pub enum DeleteFolderError {
+    /// The prefix names the account root.
+    EmptyPrefix,
+    /// Fetching the account username failed.
+    GetSettings(Error<GetSettingsError>),
+    /// Listing the folder failed.
+    ListImages(Error<ListImagesError>),
+}
+
+impl fmt::Display for DeleteFolderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeleteFolderError::EmptyPrefix => write!(f, "refusing to delete the root folder"),
+            DeleteFolderError::GetSettings(e) => write!(f, "error fetching username: {e}"),
+            DeleteFolderError::ListImages(e) => write!(f, "error listing folder: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DeleteFolderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DeleteFolderError::EmptyPrefix => None,
+            DeleteFolderError::GetSettings(e) => Some(e),
+            DeleteFolderError::ListImages(e) => Some(e),
+        }
+    }
+}
+
+/// Deletes every path below `prefix` (e.g. `campaigns/2023`).
+///
+/// Only paths inside the folder are deleted, through
+/// [`images_api::delete_image_path`], so an image that is also published
+/// outside the folder is unlinked rather than destroyed. Images listed without
+/// paths are left alone and reported in [`DeleteFolderReport::skipped`]. With
+/// [`BulkDeleteOptions::dry_run`] the folder is listed but nothing is deleted.
+pub async fn delete_folder(
+    configuration: &Configuration,
+    prefix: &str,
+    options: &BulkDeleteOptions,
+) -> Result<DeleteFolderReport, DeleteFolderError> {
+    let prefix = normalize(prefix);
+    if prefix.is_empty() {
+        return Err(DeleteFolderError::EmptyPrefix);
+    }
+    let username = match &options.username {
+        Some(username) => username.clone(),
+        None => {
+            settings_api::get_settings(configuration)
+                .await
+                .map_err(DeleteFolderError::GetSettings)?
+                .settings
+                .username
+        }
+    };
+    let tree = folder_tree(configuration, Some(&prefix), FolderTreeOptions::default())
+        .await
+        .map_err(DeleteFolderError::ListImages)?;
+
+    let user_prefix = format!("{username}/");
+    let folder_prefix = format!("{prefix}/");
+    let mut seen = HashSet::new();
+    let mut images = Vec::new();
+    let mut skipped = Vec::new();
+    let mut targets = Vec::new();
+    for node in tree.walk() {
+        for image in node.images.iter().filter(|i| seen.insert(i.id.clone())) {
+            if image.paths.is_empty() {
+                skipped.push(image.id.clone());
+                continue;
+            }
+            let (inside, outside): (Vec<String>, Vec<String>) = image
+                .paths
+                .iter()
+                .map(|p| {
+                    let p = normalize(p);
+                    p.strip_prefix(&user_prefix).map(str::to_owned).unwrap_or(p)
+                })
+                .partition(|p| p.starts_with(&folder_prefix));
+            if inside.is_empty() {
+                // Matched by the listing filter but not below `prefix/`,
+                // e.g. `campaigns/2023-old` for the folder `campaigns/2023`.
+                continue;
+            }
+            targets.extend(inside.iter().cloned().map(DeleteTarget::Path));
+            images.push((image.id.clone(), inside.len(), outside));
+        }
+    }
+
+    let options = BulkDeleteOptions {
+        username: Some(username),
+        ..options.clone()
+    };
+    let report = bulk::delete_many(configuration, targets, &options)
+        .await
+        .map_err(DeleteFolderError::GetSettings)?;
+    let mut results = report.results.into_iter();
+    let images = images
+        .into_iter()
+        .map(|(id, count, outside_paths)| FolderImageDeletion {
+            id,
+            paths: results.by_ref().take(count).collect(),
+            outside_paths,
+        })
+        .collect();
+    Ok(DeleteFolderReport {
+        prefix,
+        images,
+        skipped,
+    })
+}
+
+fn normalize(path: &str) -> String {
+    path.trim_matches('/').to_owned()
+}
+
+fn join(parent: &str, name: &str) -> String {
+    let name = normalize(name);
+    if parent.is_empty() {
+        name
+    } else {
+        format!("{parent}/{name}")
+    }
+}
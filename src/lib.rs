@@ -8,4 +8,18 @@ extern crate serde_repr;
 extern crate url;
 
 pub mod apis;
+pub mod bulk;
+pub mod cdn;
+pub mod client;
+pub mod dedup;
+pub mod error;
+pub mod folders;
+// Generated models spell out their `Default` impls.
+#[allow(clippy::derivable_impls)]
 pub mod models;
+pub mod pagination;
+pub mod preset_sync;
+pub mod sync;
+
+pub use client::ImgSrcClient;
+pub use error::ImgSrcError;
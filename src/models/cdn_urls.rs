@@ -51,4 +51,16 @@ impl CdnUrls {
             jxl,
         }
     }
+
+    /// URL of the image in `format`, if the API delivers that format.
+    pub fn get(&self, format: &models::OutputFormat) -> Option<&str> {
+        match format {
+            models::OutputFormat::Webp => Some(&self.webp),
+            models::OutputFormat::Avif => Some(&self.avif),
+            models::OutputFormat::Jpeg => Some(&self.jpeg),
+            models::OutputFormat::Png => Some(&self.png),
+            models::OutputFormat::Jxl => Some(&self.jxl),
+            models::OutputFormat::Unknown(_) => None,
+        }
+    }
 }
@@ -0,0 +1,49 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// Key name
+    #[serde(rename = "name")]
+    pub name: String,
+    /// Granted scopes
+    #[serde(rename = "scopes", skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<Scopes>>,
+    /// Expiration in days (optional, max 365)
+    #[serde(rename = "expires_in_days", skip_serializing_if = "Option::is_none")]
+    pub expires_in_days: Option<i32>,
+}
+
+impl CreateApiKeyRequest {
+    pub fn new(name: String) -> CreateApiKeyRequest {
+        CreateApiKeyRequest {
+            name,
+            scopes: None,
+            expires_in_days: None,
+        }
+    }
+}
+/// Granted scopes
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Scopes {
+    #[serde(rename = "read")]
+    Read,
+    #[serde(rename = "write")]
+    Write,
+}
+
+impl Default for Scopes {
+    fn default() -> Scopes {
+        Self::Read
+    }
+}
@@ -0,0 +1,59 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::apis::configuration::Secret;
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateApiKeyResponse {
+    /// API key UUID
+    #[serde(rename = "id")]
+    pub id: String,
+    /// Full API key (shown ONLY ONCE), redacted in `Debug` output
+    #[serde(rename = "key")]
+    pub key: Secret,
+    /// Key prefix
+    #[serde(rename = "key_prefix")]
+    pub key_prefix: String,
+    /// Key name
+    #[serde(rename = "name")]
+    pub name: String,
+    /// Granted scopes
+    #[serde(rename = "scopes")]
+    pub scopes: String,
+    /// Creation timestamp
+    #[serde(rename = "created_at")]
+    pub created_at: i64,
+    /// Expiration timestamp
+    #[serde(rename = "expires_at", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+impl CreateApiKeyResponse {
+    pub fn new(
+        id: String,
+        key: Secret,
+        key_prefix: String,
+        name: String,
+        scopes: String,
+        created_at: i64,
+    ) -> CreateApiKeyResponse {
+        CreateApiKeyResponse {
+            id,
+            key,
+            key_prefix,
+            name,
+            scopes,
+            created_at,
+            expires_at: None,
+        }
+    }
+}
@@ -21,14 +21,11 @@ pub struct CreatePresetRequest {
     pub description: Option<String>,
     /// Transformation parameters
     #[serde(rename = "params")]
-    pub params: std::collections::HashMap<String, serde_json::Value>,
+    pub params: models::PresetParams,
 }
 
 impl CreatePresetRequest {
-    pub fn new(
-        name: String,
-        params: std::collections::HashMap<String, serde_json::Value>,
-    ) -> CreatePresetRequest {
+    pub fn new(name: String, params: models::PresetParams) -> CreatePresetRequest {
         CreatePresetRequest {
             name,
             description: None,
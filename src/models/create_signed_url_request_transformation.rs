@@ -0,0 +1,42 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateSignedUrlRequestTransformation {
+    #[serde(rename = "width", skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    #[serde(rename = "height", skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    #[serde(rename = "fit", skip_serializing_if = "Option::is_none")]
+    pub fit: Option<Fit>,
+    #[serde(rename = "quality", skip_serializing_if = "Option::is_none")]
+    pub quality: Option<models::Quality>,
+    #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
+    pub format: Option<Format>,
+}
+
+impl CreateSignedUrlRequestTransformation {
+    pub fn new() -> CreateSignedUrlRequestTransformation {
+        CreateSignedUrlRequestTransformation {
+            width: None,
+            height: None,
+            fit: None,
+            quality: None,
+            format: None,
+        }
+    }
+}
+/// How the image is fitted to the requested dimensions
+pub type Fit = models::Fit;
+/// Output image format
+pub type Format = models::OutputFormat;
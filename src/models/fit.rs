@@ -0,0 +1,87 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// How an image is fitted to the requested dimensions
+///
+/// Shared by settings, presets, signed URLs and [`crate::cdn::UrlBuilder`].
+/// Unrecognized values are kept in [`Fit::Unknown`].
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Fit {
+    Cover,
+    Contain,
+    Fill,
+    ScaleDown,
+    Unknown(String),
+}
+
+impl Fit {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Cover => "cover",
+            Self::Contain => "contain",
+            Self::Fill => "fill",
+            Self::ScaleDown => "scale-down",
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl Default for Fit {
+    fn default() -> Fit {
+        Self::Cover
+    }
+}
+
+impl fmt::Display for Fit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for Fit {
+    fn from(value: String) -> Fit {
+        match value.as_str() {
+            "cover" => Self::Cover,
+            "contain" => Self::Contain,
+            "fill" => Self::Fill,
+            "scale-down" => Self::ScaleDown,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<&str> for Fit {
+    fn from(value: &str) -> Fit {
+        Fit::from(value.to_owned())
+    }
+}
+
+impl From<Fit> for String {
+    fn from(value: Fit) -> String {
+        match value {
+            Fit::Unknown(value) => value,
+            known => known.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for Fit {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Fit, Self::Err> {
+        Ok(Fit::from(s))
+    }
+}
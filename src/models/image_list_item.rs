@@ -24,7 +24,7 @@ pub struct ImageListItem {
     pub sanitized_filename: Option<String>,
     /// Image visibility (public or private)
     #[serde(rename = "visibility")]
-    pub visibility: String,
+    pub visibility: models::Visibility,
     /// File size in bytes
     #[serde(rename = "size")]
     pub size: i64,
@@ -48,7 +48,7 @@ impl ImageListItem {
     pub fn new(
         id: String,
         original_filename: String,
-        visibility: String,
+        visibility: models::Visibility,
         size: i64,
         uploaded_at: String,
         url: String,
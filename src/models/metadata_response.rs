@@ -18,7 +18,7 @@ pub struct MetadataResponse {
     pub id: String,
     /// Image visibility (public or private)
     #[serde(rename = "visibility")]
-    pub visibility: String,
+    pub visibility: models::Visibility,
     #[serde(rename = "metadata")]
     pub metadata: Box<models::ImageMetadata>,
     #[serde(rename = "urls")]
@@ -30,7 +30,7 @@ pub struct MetadataResponse {
 impl MetadataResponse {
     pub fn new(
         id: String,
-        visibility: String,
+        visibility: models::Visibility,
         metadata: models::ImageMetadata,
         urls: models::CdnUrls,
         _links: models::HateoasLinks,
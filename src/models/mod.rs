@@ -1,17 +1,29 @@
 pub mod active_signed_url;
 pub use self::active_signed_url::ActiveSignedUrl;
+pub mod api_key_list_response;
+pub use self::api_key_list_response::ApiKeyListResponse;
+pub mod api_key_response;
+pub use self::api_key_response::ApiKeyResponse;
 pub mod available_formats;
 pub use self::available_formats::AvailableFormats;
 pub mod cdn_urls;
 pub use self::cdn_urls::CdnUrls;
+pub mod create_api_key_request;
+pub use self::create_api_key_request::CreateApiKeyRequest;
+pub mod create_api_key_response;
+pub use self::create_api_key_response::CreateApiKeyResponse;
 pub mod create_preset_request;
 pub use self::create_preset_request::CreatePresetRequest;
 pub mod create_signed_url_request;
 pub use self::create_signed_url_request::CreateSignedUrlRequest;
+pub mod create_signed_url_request_transformation;
+pub use self::create_signed_url_request_transformation::CreateSignedUrlRequestTransformation;
 pub mod credits;
 pub use self::credits::Credits;
 pub mod current_period;
 pub use self::current_period::CurrentPeriod;
+pub mod delete_api_key_response;
+pub use self::delete_api_key_response::DeleteApiKeyResponse;
 pub mod delete_preset_response;
 pub use self::delete_preset_response::DeletePresetResponse;
 pub mod delete_response;
@@ -20,10 +32,14 @@ pub mod error_detail;
 pub use self::error_detail::ErrorDetail;
 pub mod error_response;
 pub use self::error_response::ErrorResponse;
+pub mod fit;
+pub use self::fit::Fit;
 pub mod folder_item;
 pub use self::folder_item::FolderItem;
 pub mod hateoas_links;
 pub use self::hateoas_links::HateoasLinks;
+pub mod health_response;
+pub use self::health_response::HealthResponse;
 pub mod image_dimensions;
 pub use self::image_dimensions::ImageDimensions;
 pub mod image_list_item;
@@ -36,12 +52,18 @@ pub mod list_presets_response;
 pub use self::list_presets_response::ListPresetsResponse;
 pub mod metadata_response;
 pub use self::metadata_response::MetadataResponse;
+pub mod output_format;
+pub use self::output_format::OutputFormat;
 pub mod path_delete_response;
 pub use self::path_delete_response::PathDeleteResponse;
 pub mod plan_limits;
 pub use self::plan_limits::PlanLimits;
 pub mod preset;
 pub use self::preset::Preset;
+pub mod preset_params;
+pub use self::preset_params::PresetParams;
+pub mod quality;
+pub use self::quality::Quality;
 pub mod search_response;
 pub use self::search_response::SearchResponse;
 pub mod search_result;
@@ -66,3 +88,5 @@ pub mod usage_response;
 pub use self::usage_response::UsageResponse;
 pub mod user_settings;
 pub use self::user_settings::UserSettings;
+pub mod visibility;
+pub use self::visibility::Visibility;
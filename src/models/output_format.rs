@@ -0,0 +1,90 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Image format an image is delivered in
+///
+/// Shared by settings, presets, signed URLs and [`crate::cdn::UrlBuilder`].
+/// Unrecognized values are kept in [`OutputFormat::Unknown`].
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum OutputFormat {
+    Webp,
+    Avif,
+    Jpeg,
+    Png,
+    Jxl,
+    Unknown(String),
+}
+
+impl OutputFormat {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+            Self::Jpeg => "jpeg",
+            Self::Png => "png",
+            Self::Jxl => "jxl",
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl Default for OutputFormat {
+    fn default() -> OutputFormat {
+        Self::Webp
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for OutputFormat {
+    fn from(value: String) -> OutputFormat {
+        match value.as_str() {
+            "webp" => Self::Webp,
+            "avif" => Self::Avif,
+            "jpeg" => Self::Jpeg,
+            "png" => Self::Png,
+            "jxl" => Self::Jxl,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<&str> for OutputFormat {
+    fn from(value: &str) -> OutputFormat {
+        OutputFormat::from(value.to_owned())
+    }
+}
+
+impl From<OutputFormat> for String {
+    fn from(value: OutputFormat) -> String {
+        match value {
+            OutputFormat::Unknown(value) => value,
+            known => known.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<OutputFormat, Self::Err> {
+        Ok(OutputFormat::from(s))
+    }
+}
@@ -24,7 +24,7 @@ pub struct Preset {
     pub description: Option<String>,
     /// Transformation parameters
     #[serde(rename = "params")]
-    pub params: std::collections::HashMap<String, serde_json::Value>,
+    pub params: models::PresetParams,
     /// Creation timestamp (Unix epoch)
     #[serde(rename = "created_at")]
     pub created_at: i64,
@@ -40,7 +40,7 @@ impl Preset {
     pub fn new(
         id: String,
         name: String,
-        params: std::collections::HashMap<String, serde_json::Value>,
+        params: models::PresetParams,
         created_at: i64,
         updated_at: i64,
         usage_count: i32,
@@ -0,0 +1,207 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+use crate::models;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Keys the API knows, with common misspellings that it would silently keep
+/// as inert parameters.
+const MISSPELLINGS: [(&str, &str); 6] = [
+    ("width", "w"),
+    ("height", "h"),
+    ("quality", "q"),
+    ("f", "format"),
+    ("fmt", "format"),
+    ("fit_mode", "fit"),
+];
+const KNOWN_KEYS: [&str; 5] = ["w", "h", "fit", "q", "format"];
+
+/// Transformation parameters of a preset
+///
+/// Serialized with the short keys used on the wire (`w`, `h`, `fit`, `q`,
+/// `format`). Keys this SDK does not know are kept in `extra`, and so are
+/// known keys whose value does not parse, so that one odd preset returned by
+/// the API does not fail a whole listing. [`validate`](PresetParams::validate)
+/// reports both before a preset is created.
+#[derive(Clone, Default, Debug, PartialEq, Serialize)]
+pub struct PresetParams {
+    /// Target width in pixels
+    #[serde(rename = "w", skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// Target height in pixels
+    #[serde(rename = "h", skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(rename = "fit", skip_serializing_if = "Option::is_none")]
+    pub fit: Option<models::Fit>,
+    #[serde(rename = "q", skip_serializing_if = "Option::is_none")]
+    pub quality: Option<models::Quality>,
+    #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
+    pub format: Option<models::OutputFormat>,
+    /// Parameters not modelled above, sent unchanged
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl PresetParams {
+    pub fn new() -> PresetParams {
+        PresetParams::default()
+    }
+
+    /// Checks for values and keys the API would reject or ignore.
+    pub fn validate(&self) -> Result<(), PresetParamsError> {
+        if let Some(width) = self.width.filter(|w| *w == 0) {
+            return Err(PresetParamsError::Width(width));
+        }
+        if let Some(height) = self.height.filter(|h| *h == 0) {
+            return Err(PresetParamsError::Height(height));
+        }
+        if let Some(quality) = self.quality.filter(|q| !q.is_valid()) {
+            return Err(PresetParamsError::Quality(quality.get()));
+        }
+        let mut keys: Vec<&String> = self.extra.keys().collect();
+        keys.sort();
+        for key in keys {
+            if KNOWN_KEYS.contains(&key.as_str()) {
+                return Err(if self.is_set(key) {
+                    PresetParamsError::DuplicateKey(key.clone())
+                } else {
+                    PresetParamsError::InvalidValue(key.clone())
+                });
+            }
+            if let Some((_, expected)) = MISSPELLINGS.iter().find(|(m, _)| m == key) {
+                return Err(PresetParamsError::MisspelledKey {
+                    key: key.clone(),
+                    expected,
+                });
+            }
+        }
+        if self == &PresetParams::default() {
+            return Err(PresetParamsError::Empty);
+        }
+        Ok(())
+    }
+
+    /// Whether the typed field for the wire key `key` is set.
+    fn is_set(&self, key: &str) -> bool {
+        match key {
+            "w" => self.width.is_some(),
+            "h" => self.height.is_some(),
+            "fit" => self.fit.is_some(),
+            "q" => self.quality.is_some(),
+            "format" => self.format.is_some(),
+            _ => false,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PresetParams {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<PresetParams, D::Error> {
+        HashMap::<String, serde_json::Value>::deserialize(deserializer).map(PresetParams::from)
+    }
+}
+
+/// Removes `key` from `extra` if its value parses as `T`; a `null` is dropped.
+fn take<T: DeserializeOwned>(
+    extra: &mut HashMap<String, serde_json::Value>,
+    key: &str,
+) -> Option<T> {
+    let value = extra.remove(key)?;
+    if value.is_null() {
+        return None;
+    }
+    match T::deserialize(&value) {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            extra.insert(key.to_owned(), value);
+            None
+        }
+    }
+}
+
+impl From<PresetParams> for HashMap<String, serde_json::Value> {
+    fn from(params: PresetParams) -> HashMap<String, serde_json::Value> {
+        let mut map = params.extra;
+        if let Some(width) = params.width {
+            map.insert("w".into(), width.into());
+        }
+        if let Some(height) = params.height {
+            map.insert("h".into(), height.into());
+        }
+        if let Some(fit) = params.fit {
+            map.insert("fit".into(), String::from(fit).into());
+        }
+        if let Some(quality) = params.quality {
+            map.insert("q".into(), quality.get().into());
+        }
+        if let Some(format) = params.format {
+            map.insert("format".into(), String::from(format).into());
+        }
+        map
+    }
+}
+
+impl From<HashMap<String, serde_json::Value>> for PresetParams {
+    fn from(mut extra: HashMap<String, serde_json::Value>) -> PresetParams {
+        PresetParams {
+            width: take(&mut extra, "w"),
+            height: take(&mut extra, "h"),
+            fit: take(&mut extra, "fit"),
+            quality: take(&mut extra, "q"),
+            format: take(&mut extra, "format"),
+            extra,
+        }
+    }
+}
+
+/// Error returned by [`PresetParams::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PresetParamsError {
+    /// Width of zero.
+    Width(u32),
+    /// Height of zero.
+    Height(u32),
+    /// Quality outside `1..=100`.
+    Quality(i32),
+    /// A known parameter was also set through `extra`.
+    DuplicateKey(String),
+    /// A known parameter whose value could not be parsed, kept in `extra`.
+    InvalidValue(String),
+    /// An `extra` key that looks like a misspelled known parameter.
+    MisspelledKey { key: String, expected: &'static str },
+    /// No parameter is set.
+    Empty,
+}
+
+impl fmt::Display for PresetParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PresetParamsError::Width(w) => write!(f, "width {w} is not a positive number"),
+            PresetParamsError::Height(h) => write!(f, "height {h} is not a positive number"),
+            PresetParamsError::Quality(q) => write!(f, "quality {q} is outside 1..=100"),
+            PresetParamsError::DuplicateKey(key) => {
+                write!(f, "parameter `{key}` is also set in extra parameters")
+            }
+            PresetParamsError::InvalidValue(key) => {
+                write!(f, "parameter `{key}` has an invalid value")
+            }
+            PresetParamsError::MisspelledKey { key, expected } => {
+                write!(f, "unknown parameter `{key}`, did you mean `{expected}`?")
+            }
+            PresetParamsError::Empty => write!(f, "preset has no parameters"),
+        }
+    }
+}
+
+impl error::Error for PresetParamsError {}
@@ -0,0 +1,121 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use std::error;
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Image quality from 1 to 100
+///
+/// Out-of-range values are rejected when constructing, and by
+/// [`PresetParams::validate`](crate::models::PresetParams::validate).
+/// Serialization passes any value through in both directions, so a value the
+/// API reports outside the range neither fails the response nor a later
+/// write of the same model; check it with [`is_valid`](Quality::is_valid).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Quality(i32);
+
+impl Quality {
+    pub const MIN: Quality = Quality(1);
+    pub const MAX: Quality = Quality(100);
+
+    /// Returns `None` if `value` is outside `1..=100`.
+    pub const fn new(value: u8) -> Option<Quality> {
+        let value = value as i32;
+        if value >= Self::MIN.0 && value <= Self::MAX.0 {
+            Some(Quality(value))
+        } else {
+            None
+        }
+    }
+
+    pub const fn get(self) -> i32 {
+        self.0
+    }
+
+    /// Whether the value is inside `1..=100`. Always true unless the value
+    /// was read from a response.
+    pub const fn is_valid(self) -> bool {
+        self.0 >= Self::MIN.0 && self.0 <= Self::MAX.0
+    }
+}
+
+impl Default for Quality {
+    fn default() -> Quality {
+        Quality(85)
+    }
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for Quality {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Quality {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Quality, D::Error> {
+        let value = i64::deserialize(deserializer)?;
+        i32::try_from(value)
+            .map(Quality)
+            .map_err(|_| de::Error::custom(QualityOutOfRange(value)))
+    }
+}
+
+/// Error for a quality outside `1..=100`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QualityOutOfRange(pub i64);
+
+impl fmt::Display for QualityOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "quality {} is outside 1..=100", self.0)
+    }
+}
+
+impl error::Error for QualityOutOfRange {}
+
+impl TryFrom<i64> for Quality {
+    type Error = QualityOutOfRange;
+
+    fn try_from(value: i64) -> Result<Quality, Self::Error> {
+        u8::try_from(value)
+            .ok()
+            .and_then(Quality::new)
+            .ok_or(QualityOutOfRange(value))
+    }
+}
+
+impl TryFrom<i32> for Quality {
+    type Error = QualityOutOfRange;
+
+    fn try_from(value: i32) -> Result<Quality, Self::Error> {
+        Quality::try_from(i64::from(value))
+    }
+}
+
+impl TryFrom<u8> for Quality {
+    type Error = QualityOutOfRange;
+
+    fn try_from(value: u8) -> Result<Quality, Self::Error> {
+        Quality::new(value).ok_or(QualityOutOfRange(value.into()))
+    }
+}
+
+impl From<Quality> for i32 {
+    fn from(value: Quality) -> i32 {
+        value.0
+    }
+}
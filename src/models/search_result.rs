@@ -27,7 +27,7 @@ pub struct SearchResult {
     pub paths: Vec<String>,
     /// Image visibility (public or private)
     #[serde(rename = "visibility")]
-    pub visibility: String,
+    pub visibility: models::Visibility,
     /// File size in bytes
     #[serde(rename = "size")]
     pub size: i64,
@@ -47,7 +47,7 @@ impl SearchResult {
         id: String,
         original_filename: String,
         paths: Vec<String>,
-        visibility: String,
+        visibility: models::Visibility,
         size: i64,
         uploaded_at: String,
         url: String,
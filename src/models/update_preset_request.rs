@@ -21,7 +21,7 @@ pub struct UpdatePresetRequest {
     pub description: Option<String>,
     /// New transformation parameters
     #[serde(rename = "params", skip_serializing_if = "Option::is_none")]
-    pub params: Option<std::collections::HashMap<String, serde_json::Value>>,
+    pub params: Option<models::PresetParams>,
 }
 
 impl UpdatePresetRequest {
@@ -15,13 +15,13 @@ use serde::{Deserialize, Serialize};
 pub struct UpdateSettingsRequest {
     /// Preferred delivery formats (ordered)
     #[serde(rename = "delivery_formats", skip_serializing_if = "Option::is_none")]
-    pub delivery_formats: Option<Vec<String>>,
+    pub delivery_formats: Option<Vec<models::OutputFormat>>,
     /// Default image quality (1-100)
     #[serde(rename = "default_quality", skip_serializing_if = "Option::is_none")]
-    pub default_quality: Option<i32>,
+    pub default_quality: Option<models::Quality>,
     /// Default fit mode
     #[serde(rename = "default_fit_mode", skip_serializing_if = "Option::is_none")]
-    pub default_fit_mode: Option<String>,
+    pub default_fit_mode: Option<models::Fit>,
     /// Default maximum width (null to clear)
     #[serde(rename = "default_max_width", skip_serializing_if = "Option::is_none")]
     pub default_max_width: Option<i32>,
@@ -15,11 +15,11 @@ use serde::{Deserialize, Serialize};
 pub struct UpdateVisibilityRequest {
     /// Image visibility
     #[serde(rename = "visibility")]
-    pub visibility: String,
+    pub visibility: models::Visibility,
 }
 
 impl UpdateVisibilityRequest {
-    pub fn new(visibility: String) -> UpdateVisibilityRequest {
+    pub fn new(visibility: models::Visibility) -> UpdateVisibilityRequest {
         UpdateVisibilityRequest { visibility }
     }
 }
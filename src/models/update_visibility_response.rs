@@ -18,14 +18,18 @@ pub struct UpdateVisibilityResponse {
     pub id: String,
     /// Image visibility
     #[serde(rename = "visibility")]
-    pub visibility: String,
+    pub visibility: models::Visibility,
     /// Success message
     #[serde(rename = "message")]
     pub message: String,
 }
 
 impl UpdateVisibilityResponse {
-    pub fn new(id: String, visibility: String, message: String) -> UpdateVisibilityResponse {
+    pub fn new(
+        id: String,
+        visibility: models::Visibility,
+        message: String,
+    ) -> UpdateVisibilityResponse {
         UpdateVisibilityResponse {
             id,
             visibility,
@@ -30,7 +30,7 @@ pub struct UploadResponse {
     pub is_new: Option<bool>,
     /// Image visibility (public or private)
     #[serde(rename = "visibility")]
-    pub visibility: String,
+    pub visibility: models::Visibility,
     /// File size in bytes
     #[serde(rename = "size")]
     pub size: i64,
@@ -54,7 +54,7 @@ impl UploadResponse {
         hash: String,
         url: String,
         paths: Vec<String>,
-        visibility: String,
+        visibility: models::Visibility,
         size: i64,
         format: String,
         available_formats: models::AvailableFormats,
@@ -27,13 +27,13 @@ pub struct UserSettings {
     pub plan: String,
     /// Preferred delivery formats (ordered)
     #[serde(rename = "delivery_formats")]
-    pub delivery_formats: Vec<String>,
+    pub delivery_formats: Vec<models::OutputFormat>,
     /// Default image quality (1-100)
     #[serde(rename = "default_quality")]
-    pub default_quality: i32,
+    pub default_quality: models::Quality,
     /// Default fit mode
     #[serde(rename = "default_fit_mode")]
-    pub default_fit_mode: String,
+    pub default_fit_mode: models::Fit,
     /// Default maximum width
     #[serde(rename = "default_max_width", skip_serializing_if = "Option::is_none")]
     pub default_max_width: Option<i32>,
@@ -65,9 +65,9 @@ impl UserSettings {
         id: String,
         username: String,
         plan: String,
-        delivery_formats: Vec<String>,
-        default_quality: i32,
-        default_fit_mode: String,
+        delivery_formats: Vec<models::OutputFormat>,
+        default_quality: models::Quality,
+        default_fit_mode: models::Fit,
         theme: String,
         language: String,
         created_at: i64,
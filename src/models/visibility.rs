@@ -0,0 +1,81 @@
+/*
+ * img-src API
+ *
+ * Image processing and delivery API.  A serverless image processing and delivery API built on Cloudflare Workers with parameter-driven image transformation and on-demand transcoding.  ## Features  - **Image Upload**: Store original images in R2 with SHA256-based deduplication - **On-Demand Transformation**: Resize, crop, and convert images via URL parameters - **Format Conversion**: WebP, AVIF, JPEG, PNG output formats - **Path Organization**: Organize images into folders with multiple paths per image - **CDN Caching**: Automatic edge caching for transformed images  ## Authentication  Authenticate using API Keys with `imgsrc_` prefix. Create your API key at https://img-src.io/settings  ## Rate Limiting  - **Free Plan**: 100 requests/minute - **Pro Plan**: 500 requests/minute  Rate limit headers are included in all responses.
+ *
+ * The version of the OpenAPI document: 1.0.0
+ * Contact: taehun@taehun.dev
+ * Generated by: https://openapi-generator.tech
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Image visibility
+///
+/// Values other than `public` and `private` are kept in
+/// [`Visibility::Unknown`] so newer server responses still deserialize.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Visibility {
+    Public,
+    Private,
+    Unknown(String),
+}
+
+impl Visibility {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Public => "public",
+            Self::Private => "private",
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl Default for Visibility {
+    fn default() -> Visibility {
+        Self::Public
+    }
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for Visibility {
+    fn from(value: String) -> Visibility {
+        match value.as_str() {
+            "public" => Self::Public,
+            "private" => Self::Private,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<&str> for Visibility {
+    fn from(value: &str) -> Visibility {
+        Visibility::from(value.to_owned())
+    }
+}
+
+impl From<Visibility> for String {
+    fn from(value: Visibility) -> String {
+        match value {
+            Visibility::Unknown(value) => value,
+            known => known.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for Visibility {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Visibility, Self::Err> {
+        Ok(Visibility::from(s))
+    }
+}
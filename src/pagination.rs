@@ -0,0 +1,167 @@
+//! Paginated streams over list endpoints.
+//!
+//! [`list_images_stream`] walks [`images_api::list_images`] page by page and
+//! yields individual images, so callers do not have to track `limit` and
+//! `offset` themselves:
+//!
+//! ```no_run
+//! # async fn run(config: &img_src::apis::configuration::Configuration) -> Result<(), Box<dyn std::error::Error>> {
+//! use futures_util::TryStreamExt;
+//! use img_src::pagination::{list_images_stream, ListImagesOptions};
+//!
+//! let mut images = std::pin::pin!(list_images_stream(
+//!     config,
+//!     Some("photos"),
+//!     ListImagesOptions::default(),
+//! ));
+//! while let Some(image) = images.try_next().await? {
+//!     println!("{}", image.id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+
+use futures_util::stream::{self, Stream};
+use tokio::task::JoinHandle;
+
+use crate::apis::configuration::Configuration;
+use crate::apis::images_api::{self, ListImagesError};
+use crate::apis::Error;
+use crate::models;
+
+/// Page size and prefetch depth for [`list_images_stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListImagesOptions {
+    /// Images requested per page.
+    pub page_size: i32,
+    /// Pages fetched in the background ahead of the page being consumed.
+    /// `0` fetches the next page only once the current one is exhausted.
+    pub prefetch: usize,
+}
+
+impl Default for ListImagesOptions {
+    fn default() -> Self {
+        ListImagesOptions {
+            page_size: 50,
+            prefetch: 1,
+        }
+    }
+}
+
+type PageResult = Result<models::ImageListResponse, Error<ListImagesError>>;
+
+struct Pager {
+    configuration: Arc<Configuration>,
+    path: Option<String>,
+    options: ListImagesOptions,
+    next_offset: i32,
+    /// Total reported by the latest page, bounding how far ahead to fetch.
+    total: Option<i32>,
+    in_flight: VecDeque<JoinHandle<PageResult>>,
+    buffer: VecDeque<models::ImageListItem>,
+    seen: HashSet<String>,
+    exhausted: bool,
+}
+
+impl Pager {
+    fn may_have_more(&self) -> bool {
+        !self.exhausted && self.total.is_none_or(|total| self.next_offset < total)
+    }
+
+    fn fetch_next(&mut self) {
+        let configuration = Arc::clone(&self.configuration);
+        let path = self.path.clone();
+        let limit = self.options.page_size;
+        let offset = self.next_offset;
+        self.next_offset += limit;
+        self.in_flight.push_back(tokio::spawn(async move {
+            images_api::list_images(&configuration, Some(limit), Some(offset), path.as_deref())
+                .await
+        }));
+    }
+
+    fn stop(&mut self) {
+        self.exhausted = true;
+        for handle in self.in_flight.drain(..) {
+            handle.abort();
+        }
+    }
+
+    async fn next_item(&mut self) -> Option<Result<models::ImageListItem, Error<ListImagesError>>> {
+        loop {
+            while let Some(item) = self.buffer.pop_front() {
+                // Items shift between pages when images are added concurrently.
+                if self.seen.insert(item.id.clone()) {
+                    return Some(Ok(item));
+                }
+            }
+            if self.in_flight.is_empty() {
+                if !self.may_have_more() {
+                    return None;
+                }
+                self.fetch_next();
+            }
+            let handle = self.in_flight.pop_front()?;
+            let page = match handle.await {
+                Ok(page) => page,
+                Err(e) => Err(Error::Io(std::io::Error::other(e))),
+            };
+            match page {
+                Ok(page) => {
+                    if !page.has_more || page.images.is_empty() {
+                        self.stop();
+                    } else {
+                        self.total = Some(page.total);
+                        while self.in_flight.len() < self.options.prefetch && self.may_have_more() {
+                            self.fetch_next();
+                        }
+                    }
+                    self.buffer.extend(page.images);
+                }
+                Err(e) => {
+                    self.stop();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Pager {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Streams every image under `path`, fetching pages on demand.
+///
+/// Images already yielded are skipped if they show up again on a later page.
+/// The stream ends after the first error. Pages are fetched on spawned tokio
+/// tasks, so the stream must be polled within a tokio runtime.
+pub fn list_images_stream(
+    configuration: &Configuration,
+    path: Option<&str>,
+    options: ListImagesOptions,
+) -> impl Stream<Item = Result<models::ImageListItem, Error<ListImagesError>>> + Send + 'static {
+    let pager = Pager {
+        configuration: Arc::new(configuration.clone()),
+        path: path.map(str::to_owned),
+        options: ListImagesOptions {
+            page_size: options.page_size.max(1),
+            ..options
+        },
+        next_offset: 0,
+        total: None,
+        in_flight: VecDeque::new(),
+        buffer: VecDeque::new(),
+        seen: HashSet::new(),
+        exhausted: false,
+    };
+    stream::unfold(pager, |mut pager| async move {
+        let item = pager.next_item().await?;
+        Some((item, pager))
+    })
+}
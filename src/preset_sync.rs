@@ -0,0 +1,432 @@
+//! Declarative preset management.
+//!
+//! A [`PresetConfig`] describes the presets an account should have, usually
+//! loaded from a JSON, TOML or YAML file kept under version control.
+//! [`plan_presets`] compares it with [`presets_api::list_presets`] and returns
+//! a [`PresetPlan`] that can be reviewed (it implements `Display`) before
+//! [`apply_presets`] creates, updates or deletes presets:
+//!
+//! ```no_run
+//! # async fn run(config: &img_src::apis::configuration::Configuration) -> Result<(), Box<dyn std::error::Error>> {
+//! use img_src::preset_sync::{apply_presets, plan_presets, PresetConfig, PresetSyncOptions};
+//!
+//! let desired = PresetConfig::load("presets.json").await?;
+//! let plan = plan_presets(config, &desired, &PresetSyncOptions::default()).await?;
+//! print!("{plan}");
+//! let report = apply_presets(config, plan).await;
+//! assert!(report.is_success());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! TOML and YAML files require the `toml` and `yaml` features.
+
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::apis::configuration::Configuration;
+use crate::apis::presets_api::{
+    self, CreatePresetError, DeletePresetError, ListPresetsError, UpdatePresetError,
+};
+use crate::apis::Error;
+use crate::models::{self, preset_params::PresetParamsError};
+
+/// The presets an account should have, keyed by name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PresetConfig {
+    #[serde(default)]
+    pub presets: BTreeMap<String, DesiredPreset>,
+}
+
+/// One preset of a [`PresetConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DesiredPreset {
+    /// Left unchanged on existing presets when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub params: models::PresetParams,
+}
+
+/// Format of a preset configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Format matching the extension of `path` (`json`, `toml`, `yaml` or `yml`).
+    pub fn from_path(path: &Path) -> Option<ConfigFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(ConfigFormat::Json),
+            "toml" => Some(ConfigFormat::Toml),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Error loading or validating a [`PresetConfig`].
+#[derive(Debug)]
+pub enum PresetConfigError {
+    Io(io::Error),
+    /// The file could not be parsed.
+    Parse(Box<dyn error::Error + Send + Sync>),
+    /// The file extension is unknown, or its format's feature is disabled.
+    UnsupportedFormat(String),
+    /// A preset has parameters the API would reject or ignore.
+    InvalidParams {
+        name: String,
+        error: PresetParamsError,
+    },
+}
+
+impl fmt::Display for PresetConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresetConfigError::Io(e) => write!(f, "error reading preset config: {e}"),
+            PresetConfigError::Parse(e) => write!(f, "error parsing preset config: {e}"),
+            PresetConfigError::UnsupportedFormat(format) => {
+                write!(f, "unsupported preset config format: {format}")
+            }
+            PresetConfigError::InvalidParams { name, error } => {
+                write!(f, "invalid params for preset `{name}`: {error}")
+            }
+        }
+    }
+}
+
+impl error::Error for PresetConfigError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PresetConfigError::Io(e) => Some(e),
+            PresetConfigError::Parse(e) => Some(e.as_ref()),
+            PresetConfigError::UnsupportedFormat(_) => None,
+            PresetConfigError::InvalidParams { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for PresetConfigError {
+    fn from(e: io::Error) -> Self {
+        PresetConfigError::Io(e)
+    }
+}
+
+impl PresetConfig {
+    /// Reads and validates the file at `path`, choosing the format by extension.
+    pub async fn load(path: impl AsRef<Path>) -> Result<PresetConfig, PresetConfigError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)
+            .ok_or_else(|| PresetConfigError::UnsupportedFormat(path.display().to_string()))?;
+        let data = tokio::fs::read_to_string(path).await?;
+        PresetConfig::parse(&data, format)
+    }
+
+    /// Parses and validates `data` in the given format.
+    pub fn parse(data: &str, format: ConfigFormat) -> Result<PresetConfig, PresetConfigError> {
+        let config: PresetConfig = match format {
+            ConfigFormat::Json => {
+                serde_json::from_str(data).map_err(|e| PresetConfigError::Parse(e.into()))?
+            }
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => {
+                toml::from_str(data).map_err(|e| PresetConfigError::Parse(e.into()))?
+            }
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => {
+                serde_norway::from_str(data).map_err(|e| PresetConfigError::Parse(e.into()))?
+            }
+            #[cfg(not(feature = "toml"))]
+            ConfigFormat::Toml => {
+                return Err(PresetConfigError::UnsupportedFormat(
+                    "TOML requires the `toml` feature".into(),
+                ))
+            }
+            #[cfg(not(feature = "yaml"))]
+            ConfigFormat::Yaml => {
+                return Err(PresetConfigError::UnsupportedFormat(
+                    "YAML requires the `yaml` feature".into(),
+                ))
+            }
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks every preset with [`models::PresetParams::validate`].
+    pub fn validate(&self) -> Result<(), PresetConfigError> {
+        for (name, preset) in &self.presets {
+            preset
+                .params
+                .validate()
+                .map_err(|error| PresetConfigError::InvalidParams {
+                    name: name.clone(),
+                    error,
+                })?;
+        }
+        Ok(())
+    }
+}
+
+/// How [`plan_presets`] treats presets missing from the configuration.
+#[derive(Debug, Clone, Default)]
+pub struct PresetSyncOptions {
+    /// Delete presets that exist on the account but not in the configuration.
+    pub delete_missing: bool,
+}
+
+/// A change [`apply_presets`] makes to one preset.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresetChange {
+    Create {
+        name: String,
+        preset: DesiredPreset,
+    },
+    Update {
+        id: String,
+        name: String,
+        /// New description, if it changes.
+        description: Option<String>,
+        /// New parameters, if they change.
+        params: Option<models::PresetParams>,
+    },
+    Delete {
+        id: String,
+        name: String,
+    },
+    /// The preset already matches the configuration.
+    Unchanged {
+        id: String,
+        name: String,
+    },
+}
+
+impl PresetChange {
+    pub fn name(&self) -> &str {
+        match self {
+            PresetChange::Create { name, .. }
+            | PresetChange::Update { name, .. }
+            | PresetChange::Delete { name, .. }
+            | PresetChange::Unchanged { name, .. } => name,
+        }
+    }
+}
+
+/// Changes needed to bring the account's presets in line with a
+/// [`PresetConfig`], ordered by preset name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PresetPlan {
+    pub changes: Vec<PresetChange>,
+}
+
+impl PresetPlan {
+    /// Whether applying the plan would change anything.
+    pub fn has_changes(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| !matches!(c, PresetChange::Unchanged { .. }))
+    }
+}
+
+impl fmt::Display for PresetPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            match change {
+                PresetChange::Create { name, preset } => {
+                    writeln!(f, "+ {name} {}", params_json(&preset.params))?
+                }
+                PresetChange::Update {
+                    name,
+                    description,
+                    params,
+                    ..
+                } => {
+                    write!(f, "~ {name}")?;
+                    if let Some(params) = params {
+                        write!(f, " params={}", params_json(params))?;
+                    }
+                    if let Some(description) = description {
+                        write!(f, " description={description:?}")?;
+                    }
+                    writeln!(f)?
+                }
+                PresetChange::Delete { name, .. } => writeln!(f, "- {name}")?,
+                PresetChange::Unchanged { .. } => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn params_json(params: &models::PresetParams) -> String {
+    serde_json::to_string(params).unwrap_or_default()
+}
+
+/// Compares `desired` with the account's presets.
+pub async fn plan_presets(
+    configuration: &Configuration,
+    desired: &PresetConfig,
+    options: &PresetSyncOptions,
+) -> Result<PresetPlan, Error<ListPresetsError>> {
+    let existing = presets_api::list_presets(configuration).await?.presets;
+    Ok(diff(desired, existing, options))
+}
+
+fn diff(
+    desired: &PresetConfig,
+    existing: Vec<models::Preset>,
+    options: &PresetSyncOptions,
+) -> PresetPlan {
+    let mut existing: BTreeMap<String, models::Preset> = existing
+        .into_iter()
+        .map(|preset| (preset.name.clone(), preset))
+        .collect();
+    let mut changes: Vec<PresetChange> = desired
+        .presets
+        .iter()
+        .map(|(name, wanted)| match existing.remove(name) {
+            None => PresetChange::Create {
+                name: name.clone(),
+                preset: wanted.clone(),
+            },
+            Some(current) => {
+                let description = wanted
+                    .description
+                    .clone()
+                    .filter(|d| current.description.as_ref() != Some(d));
+                let params = Some(wanted.params.clone()).filter(|p| *p != current.params);
+                if description.is_none() && params.is_none() {
+                    PresetChange::Unchanged {
+                        id: current.id,
+                        name: current.name,
+                    }
+                } else {
+                    PresetChange::Update {
+                        id: current.id,
+                        name: current.name,
+                        description,
+                        params,
+                    }
+                }
+            }
+        })
+        .collect();
+    if options.delete_missing {
+        changes.extend(existing.into_values().map(|preset| PresetChange::Delete {
+            id: preset.id,
+            name: preset.name,
+        }));
+        changes.sort_by(|a, b| a.name().cmp(b.name()));
+    }
+    PresetPlan { changes }
+}
+
+/// Result of applying one [`PresetChange`].
+#[derive(Debug)]
+pub enum PresetOutcome {
+    Done,
+    CreateFailed(Error<CreatePresetError>),
+    UpdateFailed(Error<UpdatePresetError>),
+    DeleteFailed(Error<DeletePresetError>),
+}
+
+/// A change together with how applying it went.
+#[derive(Debug)]
+pub struct PresetSyncItem {
+    pub change: PresetChange,
+    pub outcome: PresetOutcome,
+}
+
+/// Per-preset results of [`apply_presets`], in plan order.
+#[derive(Debug, Default)]
+pub struct PresetSyncReport {
+    pub items: Vec<PresetSyncItem>,
+}
+
+impl PresetSyncReport {
+    /// Presets that were created.
+    pub fn created(&self) -> impl Iterator<Item = &PresetSyncItem> {
+        self.done(|c| matches!(c, PresetChange::Create { .. }))
+    }
+
+    /// Presets that were updated.
+    pub fn updated(&self) -> impl Iterator<Item = &PresetSyncItem> {
+        self.done(|c| matches!(c, PresetChange::Update { .. }))
+    }
+
+    /// Presets that were deleted.
+    pub fn deleted(&self) -> impl Iterator<Item = &PresetSyncItem> {
+        self.done(|c| matches!(c, PresetChange::Delete { .. }))
+    }
+
+    /// Changes that failed.
+    pub fn failed(&self) -> impl Iterator<Item = &PresetSyncItem> {
+        self.items
+            .iter()
+            .filter(|i| !matches!(i.outcome, PresetOutcome::Done))
+    }
+
+    /// Whether every change was applied.
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+
+    fn done(&self, kind: impl Fn(&PresetChange) -> bool) -> impl Iterator<Item = &PresetSyncItem> {
+        self.items
+            .iter()
+            .filter(move |i| matches!(i.outcome, PresetOutcome::Done) && kind(&i.change))
+    }
+}
+
+/// Applies `plan` one change at a time. Failed changes are recorded in the
+/// report and do not stop the remaining ones.
+pub async fn apply_presets(configuration: &Configuration, plan: PresetPlan) -> PresetSyncReport {
+    let mut items = Vec::with_capacity(plan.changes.len());
+    for change in plan.changes {
+        let outcome = match &change {
+            PresetChange::Create { name, preset } => {
+                let mut request =
+                    models::CreatePresetRequest::new(name.clone(), preset.params.clone());
+                request.description = preset.description.clone();
+                match presets_api::create_preset(configuration, Some(request)).await {
+                    Ok(_) => PresetOutcome::Done,
+                    Err(e) => PresetOutcome::CreateFailed(e),
+                }
+            }
+            PresetChange::Update {
+                id,
+                description,
+                params,
+                ..
+            } => {
+                let mut request = models::UpdatePresetRequest::new();
+                request.description = description.clone();
+                request.params = params.clone();
+                match presets_api::update_preset(configuration, id, Some(request)).await {
+                    Ok(_) => PresetOutcome::Done,
+                    Err(e) => PresetOutcome::UpdateFailed(e),
+                }
+            }
+            // Presets are deleted by name, unlike get and update.
+            PresetChange::Delete { name, .. } => {
+                match presets_api::delete_preset(configuration, name).await {
+                    Ok(_) => PresetOutcome::Done,
+                    Err(e) => PresetOutcome::DeleteFailed(e),
+                }
+            }
+            PresetChange::Unchanged { .. } => PresetOutcome::Done,
+        };
+        items.push(PresetSyncItem { change, outcome });
+    }
+    PresetSyncReport { items }
+}
@@ -0,0 +1,636 @@
+//! One-way sync of a local directory into an img-src folder.
+//!
+//! [`sync_dir`] compares the files below a directory with a [`Manifest`] of
+//! what earlier runs published. It uploads new and changed files, and
+//! removes the paths of deleted files when
+//! [`SyncOptions::delete_removed`] is set. The manifest is updated in place
+//! and is meant to be saved between runs, which makes publishing incremental
+//! and reproducible:
+//!
+//! ```no_run
+//! # async fn run(config: &img_src::apis::configuration::Configuration) -> Result<(), Box<dyn std::error::Error>> {
+//! use img_src::sync::{sync_dir, Manifest, SyncOptions};
+//!
+//! let mut manifest = Manifest::load("imgsrc-manifest.json").await?;
+//! let options = SyncOptions {
+//!     target_prefix: Some("assets".into()),
+//!     delete_removed: true,
+//!     ..SyncOptions::default()
+//! };
+//! let report = sync_dir(config, "./public/images", &mut manifest, &options).await?;
+//! manifest.save("imgsrc-manifest.json").await?;
+//! println!("{} uploaded, {} deleted", report.uploaded().count(), report.deleted().count());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use futures_util::stream::{self, StreamExt};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::apis::configuration::Configuration;
+use crate::apis::images_api::{self, DeleteImagePathError, ListImagesError, UploadImageError};
+use crate::apis::settings_api::{self, GetSettingsError};
+use crate::apis::Error;
+use crate::bulk::{self, UploadDirOptions, DEFAULT_IMAGE_EXTENSIONS};
+use crate::dedup::{self, HashIndex};
+use crate::models;
+
+/// Manifest format written by this version of the SDK.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// What earlier syncs published, keyed by path relative to the local directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Manifest {
+            version: MANIFEST_VERSION,
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+/// A published local file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// SHA-256 of the published content.
+    pub hash: String,
+    pub image_id: String,
+    /// Path the file is published at, relative to the account, as returned
+    /// by the API (which may sanitize the file name).
+    pub remote_path: String,
+    /// All paths the image had after the last upload.
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+impl Manifest {
+    /// Creates an empty manifest.
+    pub fn new() -> Manifest {
+        Manifest::default()
+    }
+
+    /// Loads a manifest saved with [`Manifest::save`], or returns an empty
+    /// one if `path` does not exist.
+    pub async fn load(path: impl AsRef<Path>) -> io::Result<Manifest> {
+        match tokio::fs::read(path).await {
+            Ok(data) => serde_json::from_slice(&data).map_err(io::Error::other),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Manifest::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes the manifest as JSON to `path`.
+    pub async fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let data = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+        tokio::fs::write(path, data).await
+    }
+}
+
+/// How [`sync_dir`] selects files and which changes it applies.
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    /// Folder the directory is published into.
+    pub target_prefix: Option<String>,
+    /// Visibility sent with every upload.
+    pub visibility: Option<models::Visibility>,
+    /// Maximum number of requests in flight.
+    pub concurrency: usize,
+    /// Extensions (without the dot, case-insensitive) to publish.
+    pub extensions: Vec<String>,
+    /// Publish files and directories whose name starts with `.`.
+    pub include_hidden: bool,
+    /// Remove the remote path of files that were deleted locally.
+    pub delete_removed: bool,
+    /// Plan the changes without applying them or touching the manifest.
+    pub dry_run: bool,
+    /// List the remote folder first, re-uploading manifest entries that are
+    /// missing remotely and adopting files that are already published.
+    pub verify_remote: bool,
+    /// Account username for path deletion and remote verification; fetched
+    /// from the settings when `None` and needed.
+    pub username: Option<String>,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        SyncOptions {
+            target_prefix: None,
+            visibility: None,
+            concurrency: 4,
+            extensions: DEFAULT_IMAGE_EXTENSIONS.map(String::from).to_vec(),
+            include_hidden: false,
+            delete_removed: false,
+            dry_run: false,
+            verify_remote: true,
+            username: None,
+        }
+    }
+}
+
+/// Why a file is uploaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadReason {
+    /// Not in the manifest.
+    New,
+    /// Content differs from the manifest.
+    Changed,
+    /// In the manifest but no longer published at its path.
+    MissingRemotely,
+}
+
+/// A change [`sync_dir`] decided on for one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncAction {
+    /// Upload the local file.
+    Upload {
+        relative_path: String,
+        target_path: Option<String>,
+        remote_path: String,
+        hash: String,
+        reason: UploadReason,
+        /// Path the previous version was published at. It is removed once the
+        /// upload succeeded, so the old image is not orphaned, or freed and
+        /// the upload retried if it still holds the path.
+        replaces: Option<String>,
+    },
+    /// The content is already published at the path; only record it.
+    Adopt {
+        relative_path: String,
+        remote_path: String,
+        hash: String,
+        /// Previous, different path of the file, removed before recording.
+        replaces: Option<String>,
+    },
+    /// Nothing to do.
+    Unchanged { relative_path: String },
+    /// Remove the remote path of a file deleted locally.
+    Delete {
+        relative_path: String,
+        remote_path: String,
+    },
+}
+
+impl SyncAction {
+    /// Path of the file relative to the synced directory.
+    pub fn relative_path(&self) -> &str {
+        match self {
+            SyncAction::Upload { relative_path, .. }
+            | SyncAction::Adopt { relative_path, .. }
+            | SyncAction::Unchanged { relative_path }
+            | SyncAction::Delete { relative_path, .. } => relative_path,
+        }
+    }
+
+    /// Remote path this action removes, if any.
+    pub fn removed_path(&self) -> Option<&str> {
+        match self {
+            SyncAction::Upload { replaces, .. } | SyncAction::Adopt { replaces, .. } => {
+                replaces.as_deref()
+            }
+            SyncAction::Delete { remote_path, .. } => Some(remote_path),
+            SyncAction::Unchanged { .. } => None,
+        }
+    }
+}
+
+/// What happened to a planned action.
+#[derive(Debug)]
+pub enum SyncOutcome {
+    /// Dry run: the action was not applied.
+    Planned,
+    /// The action was applied (or needed no request).
+    Done,
+    UploadFailed(Error<UploadImageError>),
+    DeleteFailed(Error<DeleteImagePathError>),
+}
+
+/// One file's action and outcome.
+#[derive(Debug)]
+pub struct SyncItem {
+    pub action: SyncAction,
+    pub outcome: SyncOutcome,
+}
+
+/// Per-file results of [`sync_dir`], ordered by relative path.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub items: Vec<SyncItem>,
+}
+
+impl SyncReport {
+    /// Items whose file was (or, in a dry run, would be) uploaded.
+    pub fn uploaded(&self) -> impl Iterator<Item = &SyncItem> {
+        self.applied()
+            .filter(|i| matches!(i.action, SyncAction::Upload { .. }))
+    }
+
+    /// Items whose remote path was (or would be) removed.
+    pub fn deleted(&self) -> impl Iterator<Item = &SyncItem> {
+        self.applied()
+            .filter(|i| matches!(i.action, SyncAction::Delete { .. }))
+    }
+
+    /// Items that needed no change.
+    pub fn unchanged(&self) -> impl Iterator<Item = &SyncItem> {
+        self.items.iter().filter(|i| {
+            matches!(
+                i.action,
+                SyncAction::Unchanged { .. } | SyncAction::Adopt { .. }
+            )
+        })
+    }
+
+    /// Items whose request failed.
+    pub fn failed(&self) -> impl Iterator<Item = &SyncItem> {
+        self.items.iter().filter(|i| {
+            matches!(
+                i.outcome,
+                SyncOutcome::UploadFailed(_) | SyncOutcome::DeleteFailed(_)
+            )
+        })
+    }
+
+    /// Whether every action was applied.
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+
+    fn applied(&self) -> impl Iterator<Item = &SyncItem> {
+        self.items
+            .iter()
+            .filter(|i| matches!(i.outcome, SyncOutcome::Planned | SyncOutcome::Done))
+    }
+}
+
+/// Error that stops [`sync_dir`] before any change is applied.
+#[derive(Debug)]
+pub enum SyncError {
+    /// Reading the local directory or hashing a file failed.
+    Io(io::Error),
+    /// Listing the remote folder failed.
+    ListImages(Error<ListImagesError>),
+    /// Fetching the username failed.
+    GetSettings(Error<GetSettingsError>),
+}
+
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncError::Io(e) => write!(f, "error reading local files: {e}"),
+            SyncError::ListImages(e) => write!(f, "error listing remote images: {e}"),
+            SyncError::GetSettings(e) => write!(f, "error fetching username: {e}"),
+        }
+    }
+}
+
+impl error::Error for SyncError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(match self {
+            SyncError::Io(e) => e,
+            SyncError::ListImages(e) => e,
+            SyncError::GetSettings(e) => e,
+        })
+    }
+}
+
+impl From<io::Error> for SyncError {
+    fn from(e: io::Error) -> Self {
+        SyncError::Io(e)
+    }
+}
+
+/// Brings the img-src folder in line with `dir` and updates `manifest`.
+///
+/// Failed requests are recorded in the report and leave their manifest
+/// entry untouched, so the next run retries them.
+pub async fn sync_dir(
+    configuration: &Configuration,
+    dir: impl AsRef<Path>,
+    manifest: &mut Manifest,
+    options: &SyncOptions,
+) -> Result<SyncReport, SyncError> {
+    let mut username = options.username.clone();
+    if options.verify_remote && username.is_none() {
+        username = Some(fetch_username(configuration).await?);
+    }
+    let actions = plan(
+        configuration,
+        dir.as_ref(),
+        manifest,
+        options,
+        username.as_deref(),
+    )
+    .await?;
+    if options.dry_run {
+        let items = actions
+            .into_iter()
+            .map(|action| SyncItem {
+                action,
+                outcome: SyncOutcome::Planned,
+            })
+            .collect();
+        return Ok(SyncReport { items });
+    }
+
+    // Needed to delete paths and to read back the paths of uploads.
+    let username = match username {
+        Some(username) => username,
+        None if actions
+            .iter()
+            .any(|a| a.removed_path().is_some() || matches!(a, SyncAction::Upload { .. })) =>
+        {
+            fetch_username(configuration).await?
+        }
+        None => String::new(),
+    };
+
+    let root = dir.as_ref();
+    let visibility = options.visibility.as_ref();
+    let username = username.as_str();
+    let mut items: Vec<(SyncItem, Option<ManifestEntry>)> = stream::iter(actions)
+        .map(|action| apply(configuration, root, username, visibility, action))
+        .buffer_unordered(options.concurrency.max(1))
+        .collect()
+        .await;
+    items.sort_by(|(a, _), (b, _)| a.action.relative_path().cmp(b.action.relative_path()));
+
+    let mut report = SyncReport::default();
+    for (item, entry) in items {
+        if let SyncOutcome::Done = item.outcome {
+            let relative_path = item.action.relative_path().to_owned();
+            match entry {
+                Some(entry) => {
+                    manifest.files.insert(relative_path, entry);
+                }
+                None if matches!(item.action, SyncAction::Delete { .. }) => {
+                    manifest.files.remove(&relative_path);
+                }
+                None => {}
+            }
+        }
+        report.items.push(item);
+    }
+    manifest.version = MANIFEST_VERSION;
+    Ok(report)
+}
+
+async fn fetch_username(configuration: &Configuration) -> Result<String, SyncError> {
+    Ok(settings_api::get_settings(configuration)
+        .await
+        .map_err(SyncError::GetSettings)?
+        .settings
+        .username)
+}
+
+async fn plan(
+    configuration: &Configuration,
+    dir: &Path,
+    manifest: &Manifest,
+    options: &SyncOptions,
+    username: Option<&str>,
+) -> Result<Vec<SyncAction>, SyncError> {
+    let select = UploadDirOptions {
+        target_prefix: options.target_prefix.clone(),
+        extensions: options.extensions.clone(),
+        include_hidden: options.include_hidden,
+        ..UploadDirOptions::default()
+    };
+    let files = bulk::collect_files(dir, &select).await?;
+    let remote = if options.verify_remote {
+        let root = options
+            .target_prefix
+            .as_deref()
+            .map(|p| p.trim_matches('/'))
+            .filter(|p| !p.is_empty());
+        let index = HashIndex::fetch(configuration, root)
+            .await
+            .map_err(SyncError::ListImages)?;
+        Some(match username {
+            Some(username) => index.with_username(username),
+            None => index,
+        })
+    } else {
+        None
+    };
+
+    let mut actions = Vec::new();
+    let mut present = BTreeSet::new();
+    for file in files.into_iter().filter(|f| f.skip.is_none()) {
+        present.insert(file.relative_path.clone());
+        let hash = dedup::sha256_file(&file.path).await?;
+        let file_name = file.relative_path.rsplit('/').next().unwrap_or_default();
+        let remote_path = match &file.target_path {
+            Some(target) => format!("{target}/{file_name}"),
+            None => file_name.to_owned(),
+        };
+        let previous = manifest.files.get(&file.relative_path);
+        // The recorded path is the one the API returned, which may differ from
+        // the local file name; it only has to be in the same folder.
+        let recorded = previous
+            .map(|entry| entry.remote_path.as_str())
+            .filter(|recorded| folder_of(recorded) == folder_of(&remote_path));
+        let published = remote
+            .as_ref()
+            .map(|index| index.contains_path(&hash, recorded.unwrap_or(&remote_path)));
+        let reason = match previous {
+            Some(entry) if entry.hash == hash && recorded.is_some() => {
+                if published == Some(false) {
+                    Some(UploadReason::MissingRemotely)
+                } else {
+                    None
+                }
+            }
+            Some(_) => Some(UploadReason::Changed),
+            None => Some(UploadReason::New),
+        };
+        let stale = previous
+            .filter(|_| reason == Some(UploadReason::Changed))
+            .map(|entry| entry.remote_path.clone());
+        actions.push(match reason {
+            None => SyncAction::Unchanged {
+                relative_path: file.relative_path,
+            },
+            Some(UploadReason::New | UploadReason::Changed) if published == Some(true) => {
+                SyncAction::Adopt {
+                    relative_path: file.relative_path,
+                    // The new content already holds the path; keep it.
+                    replaces: stale.filter(|stale| *stale != remote_path),
+                    remote_path,
+                    hash,
+                }
+            }
+            Some(reason) => SyncAction::Upload {
+                relative_path: file.relative_path,
+                target_path: file.target_path,
+                remote_path,
+                hash,
+                reason,
+                replaces: stale,
+            },
+        });
+    }
+
+    if options.delete_removed {
+        for (relative_path, entry) in &manifest.files {
+            if !present.contains(relative_path) {
+                actions.push(SyncAction::Delete {
+                    relative_path: relative_path.clone(),
+                    remote_path: entry.remote_path.clone(),
+                });
+            }
+        }
+    }
+    actions.sort_by(|a, b| a.relative_path().cmp(b.relative_path()));
+    Ok(actions)
+}
+
+async fn apply(
+    configuration: &Configuration,
+    root: &Path,
+    username: &str,
+    visibility: Option<&models::Visibility>,
+    action: SyncAction,
+) -> (SyncItem, Option<ManifestEntry>) {
+    let (outcome, entry) = match &action {
+        SyncAction::Unchanged { .. } => (SyncOutcome::Done, None),
+        SyncAction::Delete { remote_path, .. } => {
+            match delete_path(configuration, username, remote_path).await {
+                Ok(()) => (SyncOutcome::Done, None),
+                Err(e) => (SyncOutcome::DeleteFailed(e), None),
+            }
+        }
+        SyncAction::Adopt {
+            remote_path,
+            hash,
+            replaces,
+            ..
+        } => {
+            let entry = ManifestEntry {
+                hash: hash.clone(),
+                image_id: dedup::image_id_for_hash(hash).to_owned(),
+                remote_path: remote_path.clone(),
+                paths: vec![remote_path.clone()],
+            };
+            match replaces {
+                Some(stale) => match delete_path(configuration, username, stale).await {
+                    Ok(()) => (SyncOutcome::Done, Some(entry)),
+                    Err(e) => (SyncOutcome::DeleteFailed(e), None),
+                },
+                None => (SyncOutcome::Done, Some(entry)),
+            }
+        }
+        SyncAction::Upload {
+            relative_path,
+            target_path,
+            remote_path,
+            replaces,
+            ..
+        } => {
+            let file = root.join(relative_path);
+            let upload = || {
+                images_api::upload_image(
+                    configuration,
+                    Some(file.clone()),
+                    target_path.as_deref(),
+                    visibility.cloned(),
+                )
+            };
+            let mut freed = false;
+            let mut result = upload().await;
+            if let (Err(Error::ResponseError(content)), Some(stale)) = (&result, replaces) {
+                // The previous version still holds the path: free it and retry.
+                if content.status == StatusCode::CONFLICT {
+                    if let Err(e) = delete_path(configuration, username, stale).await {
+                        return (
+                            SyncItem {
+                                outcome: SyncOutcome::DeleteFailed(e),
+                                action,
+                            },
+                            None,
+                        );
+                    }
+                    freed = true;
+                    result = upload().await;
+                }
+            }
+            match result {
+                Ok(upload) => {
+                    let published = published_path(&upload.paths, username, remote_path);
+                    let entry = ManifestEntry {
+                        hash: upload.hash,
+                        image_id: upload.id,
+                        remote_path: published,
+                        paths: upload.paths,
+                    };
+                    let stale = replaces
+                        .as_deref()
+                        .filter(|stale| !freed && *stale != entry.remote_path);
+                    match stale {
+                        Some(stale) => match delete_path(configuration, username, stale).await {
+                            Ok(()) => (SyncOutcome::Done, Some(entry)),
+                            Err(e) => (SyncOutcome::DeleteFailed(e), None),
+                        },
+                        None => (SyncOutcome::Done, Some(entry)),
+                    }
+                }
+                Err(e) => (SyncOutcome::UploadFailed(e), None),
+            }
+        }
+    };
+    (SyncItem { action, outcome }, entry)
+}
+
+/// Folder part of an account-relative path, empty at the root.
+fn folder_of(path: &str) -> &str {
+    path.trim_matches('/')
+        .rsplit_once('/')
+        .map_or("", |(folder, _)| folder)
+}
+
+/// The path among `paths` returned by an upload that the file was published
+/// at: `expected` if the API kept the name, otherwise the one in the same
+/// folder.
+fn published_path(paths: &[String], username: &str, expected: &str) -> String {
+    let folder = folder_of(expected);
+    let candidates: Vec<&str> = paths
+        .iter()
+        .map(|path| {
+            let path = path.trim_matches('/');
+            path.strip_prefix(username)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .filter(|_| !username.is_empty())
+                .unwrap_or(path)
+        })
+        .filter(|path| folder_of(path) == folder)
+        .collect();
+    candidates
+        .iter()
+        .find(|path| **path == expected)
+        .or(candidates.first())
+        .map_or_else(|| expected.to_owned(), |path| (*path).to_owned())
+}
+
+async fn delete_path(
+    configuration: &Configuration,
+    username: &str,
+    remote_path: &str,
+) -> Result<(), Error<DeleteImagePathError>> {
+    match images_api::delete_image_path(configuration, username, remote_path).await {
+        Ok(_) => Ok(()),
+        // Already gone remotely.
+        Err(Error::ResponseError(ref content)) if content.status == StatusCode::NOT_FOUND => Ok(()),
+        Err(e) => Err(e),
+    }
+}
@@ -1,4 +1,4 @@
-use img_src::apis::configuration::{ApiKey, Configuration};
+use img_src::apis::configuration::{ApiKey, Configuration, Secret};
 use img_src::apis::{parse_deep_object, urlencode};
 use serde_json::json;
 
@@ -65,7 +65,7 @@ fn configuration_set_api_key() {
     });
     let api_key = config.api_key.unwrap();
     assert_eq!(api_key.prefix, Some("Bearer".into()));
-    assert_eq!(api_key.key, "imgsrc_abc123");
+    assert_eq!(api_key.key.expose_secret(), "imgsrc_abc123");
 }
 
 #[test]
@@ -86,6 +86,41 @@ fn configuration_clone() {
     assert_eq!(cloned.base_path, config.base_path);
 }
 
+#[test]
+fn configuration_debug_redacts_secrets() {
+    let mut config = Configuration::new();
+    config.bearer_access_token = Some("imgsrc_live_bearer".into());
+    config.oauth_access_token = Some("oauth_token_value".into());
+    config.basic_auth = Some(("user".into(), Some("hunter2".into())));
+    config.api_key = Some(ApiKey {
+        prefix: None,
+        key: "imgsrc_live_key".into(),
+    });
+
+    let debug = format!("{config:?}");
+    for secret in [
+        "imgsrc_live_bearer",
+        "oauth_token_value",
+        "hunter2",
+        "imgsrc_live_key",
+    ] {
+        assert!(!debug.contains(secret), "{secret} leaked in {debug}");
+    }
+    assert!(debug.contains("\"user\""));
+
+    let bearer = config.bearer_access_token.unwrap();
+    assert_eq!(
+        format!("{bearer:?}"),
+        format!("Secret(imgsrc_***#{})", bearer.fingerprint())
+    );
+    assert_eq!(bearer.fingerprint().len(), 8);
+    assert_ne!(
+        bearer.fingerprint(),
+        Secret::from("imgsrc_live_key").fingerprint()
+    );
+    assert!(format!("{:?}", Secret::from("hunter2")).starts_with("Secret(***#"));
+}
+
 // ============================================================
 // urlencode
 // ============================================================
@@ -202,6 +237,7 @@ fn error_display_response() {
     let err: img_src::apis::Error<String> =
         img_src::apis::Error::ResponseError(img_src::apis::ResponseContent {
             status: reqwest::StatusCode::NOT_FOUND,
+            headers: reqwest::header::HeaderMap::new(),
             content: "not found".into(),
             entity: Some("entity".into()),
         });
@@ -221,7 +257,7 @@ fn error_from_serde() {
 
 #[test]
 fn error_from_io() {
-    let io_err = std::io::Error::new(std::io::ErrorKind::Other, "test");
+    let io_err = std::io::Error::other("test");
     let err: img_src::apis::Error<()> = img_src::apis::Error::from(io_err);
     match err {
         img_src::apis::Error::Io(_) => {}
@@ -233,6 +269,7 @@ fn error_from_io() {
 fn response_content_fields() {
     let rc = img_src::apis::ResponseContent::<String> {
         status: reqwest::StatusCode::OK,
+        headers: reqwest::header::HeaderMap::new(),
         content: "{\"ok\":true}".into(),
         entity: None,
     };
@@ -317,6 +354,95 @@ fn usage_api_error_enums_deserialize() {
     let _: GetUsageError = serde_json::from_value(error_json).unwrap();
 }
 
+#[test]
+fn api_keys_api_error_enums_deserialize() {
+    use img_src::apis::api_keys_api::*;
+
+    let error_json = json!({
+        "error": {
+            "code": "NOT_FOUND",
+            "message": "API key not found",
+            "status": 404
+        }
+    });
+
+    let _: CreateApiKeyError = serde_json::from_value(error_json.clone()).unwrap();
+    let _: DeleteApiKeyError = serde_json::from_value(error_json.clone()).unwrap();
+    let _: ListApiKeysError = serde_json::from_value(error_json).unwrap();
+}
+
+#[test]
+fn health_api_error_enum_deserializes_unknown_value() {
+    use img_src::apis::health_api::HealthCheckError;
+
+    let e: HealthCheckError = serde_json::from_value(json!({ "status": "down" })).unwrap();
+    assert!(matches!(e, HealthCheckError::UnknownValue(_)));
+}
+
+// ============================================================
+// Status-aware error entities
+// ============================================================
+
+const ERROR_BODY: &str =
+    r#"{"error":{"code":"RATE_LIMITED","message":"Too many requests","status":429}}"#;
+
+#[test]
+fn error_entity_is_selected_by_status() {
+    use img_src::apis::images_api::UploadImageError;
+    use img_src::apis::FromErrorResponse;
+
+    let entity =
+        UploadImageError::from_response(reqwest::StatusCode::TOO_MANY_REQUESTS, ERROR_BODY);
+    match entity {
+        Some(UploadImageError::Status429(body)) => {
+            assert_eq!(body.error.code, "RATE_LIMITED");
+        }
+        other => panic!("expected Status429, got {:?}", other),
+    }
+
+    let entity = UploadImageError::from_response(reqwest::StatusCode::CONFLICT, ERROR_BODY);
+    assert!(matches!(entity, Some(UploadImageError::Status409(_))));
+
+    let entity = UploadImageError::from_response(reqwest::StatusCode::NOT_FOUND, ERROR_BODY);
+    assert!(matches!(entity, Some(UploadImageError::Status404(_))));
+}
+
+#[test]
+fn error_entity_undocumented_status_is_unknown_value() {
+    use img_src::apis::presets_api::GetPresetError;
+    use img_src::apis::FromErrorResponse;
+
+    let entity = GetPresetError::from_response(reqwest::StatusCode::BAD_GATEWAY, ERROR_BODY);
+    match entity {
+        Some(GetPresetError::UnknownValue(value)) => {
+            assert_eq!(value["error"]["code"], "RATE_LIMITED");
+        }
+        other => panic!("expected UnknownValue, got {:?}", other),
+    }
+}
+
+#[test]
+fn error_entity_non_error_body_is_unknown_value() {
+    use img_src::apis::usage_api::GetUsageError;
+    use img_src::apis::FromErrorResponse;
+
+    let entity =
+        GetUsageError::from_response(reqwest::StatusCode::UNAUTHORIZED, r#"{"message":"nope"}"#);
+    assert!(matches!(entity, Some(GetUsageError::UnknownValue(_))));
+}
+
+#[test]
+fn error_entity_non_json_body_is_none() {
+    use img_src::apis::settings_api::GetSettingsError;
+    use img_src::apis::FromErrorResponse;
+
+    let entity = GetSettingsError::from_response(
+        reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+        "Internal Server Error",
+    );
+    assert!(entity.is_none());
+}
+
 // ============================================================
 // ResponseContent and Error type tests
 // ============================================================
@@ -325,6 +451,7 @@ fn usage_api_error_enums_deserialize() {
 fn error_response_content_with_json_body() {
     let rc = img_src::apis::ResponseContent::<String> {
         status: reqwest::StatusCode::BAD_REQUEST,
+        headers: reqwest::header::HeaderMap::new(),
         content: r#"{"error":{"code":"VALIDATION_ERROR","message":"Invalid params","status":400}}"#
             .into(),
         entity: Some("parsed entity".into()),
@@ -338,6 +465,7 @@ fn error_response_content_with_json_body() {
 fn error_response_content_with_text_body() {
     let rc = img_src::apis::ResponseContent::<String> {
         status: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+        headers: reqwest::header::HeaderMap::new(),
         content: "Internal Server Error".into(),
         entity: None,
     };
@@ -350,6 +478,7 @@ fn error_response_content_with_text_body() {
 fn error_display_response_with_typed_entity() {
     let rc = img_src::apis::ResponseContent {
         status: reqwest::StatusCode::FORBIDDEN,
+        headers: reqwest::header::HeaderMap::new(),
         content: "forbidden".into(),
         entity: Some(
             json!({"error": {"code": "FORBIDDEN", "message": "Access denied", "status": 403}}),
@@ -372,6 +501,7 @@ fn error_source_response_error_is_none() {
     use std::error::Error as StdError;
     let rc = img_src::apis::ResponseContent::<String> {
         status: reqwest::StatusCode::NOT_FOUND,
+        headers: reqwest::header::HeaderMap::new(),
         content: "not found".into(),
         entity: None,
     };
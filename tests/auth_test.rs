@@ -0,0 +1,102 @@
+mod common;
+
+use common::make_config_without_credentials;
+use img_src::apis::auth::AuthError;
+use img_src::apis::configuration::ApiKey;
+use img_src::apis::{presets_api, Error};
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn presets_body() -> serde_json::Value {
+    json!({ "presets": [], "total": 0 })
+}
+
+/// Expects exactly one preset listing carrying `authorization`.
+async fn expect_authorization(server: &MockServer, authorization: &str) {
+    Mock::given(method("GET"))
+        .and(path("/api/v1/settings/presets"))
+        .and(header("authorization", authorization))
+        .respond_with(ResponseTemplate::new(200).set_body_json(presets_body()))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn api_key_defaults_to_bearer_prefix() {
+    let server = MockServer::start().await;
+    expect_authorization(&server, "Bearer imgsrc_key").await;
+
+    let mut config = make_config_without_credentials(&server);
+    config.api_key = Some(ApiKey {
+        prefix: None,
+        key: "imgsrc_key".into(),
+    });
+    presets_api::list_presets(&config).await.unwrap();
+}
+
+#[tokio::test]
+async fn api_key_uses_its_prefix() {
+    let server = MockServer::start().await;
+    expect_authorization(&server, "Token imgsrc_key").await;
+
+    let mut config = make_config_without_credentials(&server);
+    config.api_key = Some(ApiKey {
+        prefix: Some("Token".into()),
+        key: "imgsrc_key".into(),
+    });
+    presets_api::list_presets(&config).await.unwrap();
+}
+
+#[tokio::test]
+async fn oauth_token_is_sent_as_bearer() {
+    let server = MockServer::start().await;
+    expect_authorization(&server, "Bearer oauth_token").await;
+
+    let mut config = make_config_without_credentials(&server);
+    config.oauth_access_token = Some("oauth_token".into());
+    presets_api::list_presets(&config).await.unwrap();
+}
+
+#[tokio::test]
+async fn basic_auth_is_base64_encoded() {
+    let server = MockServer::start().await;
+    // base64("user:pass")
+    expect_authorization(&server, "Basic dXNlcjpwYXNz").await;
+
+    let mut config = make_config_without_credentials(&server);
+    config.basic_auth = Some(("user".into(), Some("pass".into())));
+    presets_api::list_presets(&config).await.unwrap();
+}
+
+#[tokio::test]
+async fn bearer_token_takes_precedence() {
+    let server = MockServer::start().await;
+    expect_authorization(&server, "Bearer imgsrc_bearer").await;
+
+    let mut config = make_config_without_credentials(&server);
+    config.bearer_access_token = Some("imgsrc_bearer".into());
+    config.api_key = Some(ApiKey {
+        prefix: None,
+        key: "imgsrc_key".into(),
+    });
+    presets_api::list_presets(&config).await.unwrap();
+}
+
+#[tokio::test]
+async fn missing_credentials_fail_without_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(presets_body()))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let err = presets_api::list_presets(&make_config_without_credentials(&server))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Auth(AuthError::MissingCredentials)));
+    assert!(err.to_string().contains("no credentials configured"));
+    assert!(err.to_string().contains("credential_provider"));
+}
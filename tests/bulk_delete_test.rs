@@ -0,0 +1,260 @@
+mod common;
+
+use common::{image_item, make_config};
+use img_src::bulk::{delete_many, BulkDeleteOptions, DeleteOutcome, DeleteTarget};
+use img_src::ImgSrcClient;
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn options() -> BulkDeleteOptions {
+    BulkDeleteOptions {
+        username: Some("alice".into()),
+        ..BulkDeleteOptions::default()
+    }
+}
+
+fn path_deleted(remaining: &[&str]) -> serde_json::Value {
+    json!({
+        "success": true,
+        "message": "Path deleted",
+        "remaining_paths": remaining,
+        "image_deleted": remaining.is_empty(),
+        "deleted_at": "2024-01-01T00:00:00Z"
+    })
+}
+
+#[tokio::test]
+async fn deletes_images_and_paths_with_aggregated_report() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/abc123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "message": "Image deleted",
+            "deleted_paths": ["alice/a.png"],
+            "deleted_at": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/path/alice/photos%2Fshared.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(path_deleted(&["alice/copy.png"])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/path/alice/photos%2Flast.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(path_deleted(&[])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/gone"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/broken"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let targets = vec![
+        DeleteTarget::Image("abc123".into()),
+        DeleteTarget::Path("/photos/shared.png".into()),
+        DeleteTarget::Path("photos/last.png".into()),
+        DeleteTarget::Image("gone".into()),
+        DeleteTarget::Image("broken".into()),
+    ];
+    let report = delete_many(&make_config(&server), targets.clone(), &options())
+        .await
+        .unwrap();
+
+    let in_order: Vec<_> = report.results.iter().map(|r| r.target.clone()).collect();
+    assert_eq!(in_order, targets);
+    assert_eq!(report.deleted().count(), 3);
+    assert_eq!(report.images_deleted(), 2);
+    assert_eq!(report.not_found().count(), 1);
+    assert_eq!(report.failed().count(), 1);
+    assert!(matches!(
+        report.results[4].outcome,
+        DeleteOutcome::Failed(_)
+    ));
+    let failure = report.failures().next().unwrap();
+    assert_eq!(failure.path, "broken");
+    assert_eq!(failure.error.status().map(|s| s.as_u16()), Some(500));
+    let remaining: Vec<_> = report.remaining_paths().collect();
+    assert_eq!(
+        remaining,
+        vec![("/photos/shared.png", &["alice/copy.png".to_string()][..])]
+    );
+    assert!(!report.is_success());
+}
+
+#[tokio::test]
+async fn dry_run_looks_targets_up_without_deleting() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images/abc123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "abc123",
+            "visibility": "public",
+            "metadata": {
+                "hash": "deadbeef",
+                "original_filename": "a.png",
+                "size": 314,
+                "uploaded_at": "2024-01-01T00:00:00Z",
+                "mime_type": "image/png"
+            },
+            "urls": {
+                "original": "o", "webp": "w", "avif": "a", "jpeg": "j", "png": "p", "jxl": "x"
+            },
+            "_links": { "self": "/api/v1/images/abc123", "delete": "/api/v1/images/abc123" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images/gone"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images/broken"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    // Both path targets share one listing of their folder.
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images"))
+        .and(query_param("path", "photos"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "images": [image_item("cat", &["alice/photos/cat.png"])],
+            "folders": [],
+            "total": 1,
+            "limit": 50,
+            "offset": 0,
+            "has_more": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ImgSrcClient::from_configuration(make_config(&server));
+    let options = BulkDeleteOptions {
+        dry_run: true,
+        ..options()
+    };
+    let report = client
+        .images()
+        .delete_many(
+            [
+                DeleteTarget::Image("abc123".into()),
+                DeleteTarget::Path("/photos/cat.png".into()),
+                DeleteTarget::Path("photos/dog.png".into()),
+                DeleteTarget::Image("gone".into()),
+                DeleteTarget::Image("broken".into()),
+            ],
+            &options,
+        )
+        .await
+        .unwrap();
+
+    let planned: Vec<_> = report.planned().map(|r| r.target.clone()).collect();
+    assert_eq!(
+        planned,
+        [
+            DeleteTarget::Image("abc123".into()),
+            DeleteTarget::Path("/photos/cat.png".into()),
+        ]
+    );
+    let not_found: Vec<_> = report.not_found().map(|r| r.target.clone()).collect();
+    assert_eq!(
+        not_found,
+        [
+            DeleteTarget::Path("photos/dog.png".into()),
+            DeleteTarget::Image("gone".into()),
+        ]
+    );
+    let failures: Vec<_> = report.failures().map(|f| f.path.as_str()).collect();
+    assert_eq!(failures, ["broken"]);
+    assert_eq!(report.deleted().count(), 0);
+}
+
+/// Answers the username lookup once, reporting `default_quality`.
+async fn mount_settings(server: &MockServer, default_quality: i64) {
+    Mock::given(method("GET"))
+        .and(path("/api/v1/settings"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "settings": {
+                "id": "user_abc123",
+                "username": "bob",
+                "email": "bob@example.com",
+                "plan": "pro",
+                "delivery_formats": ["webp"],
+                "default_quality": default_quality,
+                "default_fit_mode": "cover",
+                "default_max_width": 3840,
+                "default_max_height": 2160,
+                "theme": "dark",
+                "language": "en",
+                "created_at": 1672531200_i64,
+                "updated_at": 1719792000_i64,
+                "total_uploads": 1,
+                "storage_used_bytes": 314
+            }
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn fetches_username_for_path_targets() {
+    let server = MockServer::start().await;
+    mount_settings(&server, 85).await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/path/bob/cat.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(path_deleted(&[])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let report = delete_many(
+        &make_config(&server),
+        [DeleteTarget::Path("cat.png".into())],
+        &BulkDeleteOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.images_deleted(), 1);
+    assert!(report.is_success());
+}
+
+#[tokio::test]
+async fn out_of_range_settings_do_not_block_path_deletes() {
+    let server = MockServer::start().await;
+    mount_settings(&server, 0).await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/path/bob/cat.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(path_deleted(&[])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let report = delete_many(
+        &make_config(&server),
+        [DeleteTarget::Path("cat.png".into())],
+        &BulkDeleteOptions::default(),
+    )
+    .await
+    .unwrap();
+    assert!(report.is_success());
+}
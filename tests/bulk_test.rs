@@ -0,0 +1,166 @@
+mod common;
+
+use common::{make_config, upload_body, write};
+use img_src::bulk::{upload_dir, SkipReason, UploadDirOptions, UploadOutcome};
+use img_src::models::Visibility;
+use img_src::ImgSrcClient;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_uploads(server: &MockServer) {
+    let mut duplicate = upload_body();
+    duplicate["is_new"] = json!(false);
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .and(body_string_contains("duplicate-bytes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(duplicate))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .and(body_string_contains("broken-bytes"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(upload_body()))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn uploads_directory_and_reports_each_file() {
+    let server = MockServer::start().await;
+    mount_uploads(&server).await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "a.png", "new-bytes");
+    write(dir.path(), "photos/2024/b.JPG", "duplicate-bytes");
+    write(dir.path(), "photos/c.webp", "broken-bytes");
+    write(dir.path(), "notes.txt", "text");
+    write(dir.path(), ".cache/d.png", "hidden-dir");
+
+    let options = UploadDirOptions {
+        target_prefix: Some("/assets/".into()),
+        visibility: Some(Visibility::Private),
+        ..UploadDirOptions::default()
+    };
+    let report = upload_dir(&make_config(&server), dir.path(), &options)
+        .await
+        .unwrap();
+
+    let summary: Vec<(&str, Option<&str>)> = report
+        .files
+        .iter()
+        .map(|f| (f.relative_path.as_str(), f.target_path.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("a.png", Some("assets")),
+            ("notes.txt", Some("assets")),
+            ("photos/2024/b.JPG", Some("assets/photos/2024")),
+            ("photos/c.webp", Some("assets/photos")),
+        ]
+    );
+    assert_eq!(report.new_images().count(), 1);
+    assert_eq!(
+        report.deduplicated().next().unwrap().relative_path,
+        "photos/2024/b.JPG"
+    );
+    assert!(matches!(
+        report.skipped().next().unwrap().outcome,
+        UploadOutcome::Skipped(SkipReason::UnsupportedExtension)
+    ));
+    assert_eq!(
+        report.failed().next().unwrap().relative_path,
+        "photos/c.webp"
+    );
+    assert!(!report.is_success());
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    for request in requests {
+        assert!(String::from_utf8_lossy(&request.body).contains("private"));
+    }
+}
+
+#[tokio::test]
+async fn hidden_files_are_skipped_unless_included() {
+    let server = MockServer::start().await;
+    mount_uploads(&server).await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), ".hidden.png", "new-bytes");
+    write(dir.path(), ".cache/e.png", "new-bytes");
+
+    let client = ImgSrcClient::from_configuration(make_config(&server));
+    let report = client
+        .images()
+        .upload_dir(dir.path(), &UploadDirOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(report.files.len(), 1);
+    assert!(matches!(
+        report.files[0].outcome,
+        UploadOutcome::Skipped(SkipReason::Hidden)
+    ));
+    assert_eq!(report.files[0].target_path, None);
+
+    let options = UploadDirOptions {
+        include_hidden: true,
+        ..UploadDirOptions::default()
+    };
+    let report = client
+        .images()
+        .upload_dir(dir.path(), &options)
+        .await
+        .unwrap();
+    assert_eq!(report.new_images().count(), 2);
+    assert!(report.is_success());
+}
+
+#[tokio::test]
+async fn uploads_run_concurrently_up_to_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(upload_body())
+                .set_delay(Duration::from_millis(200)),
+        )
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    for i in 0..4 {
+        write(dir.path(), &format!("{i}.png"), "bytes");
+    }
+
+    let options = UploadDirOptions {
+        concurrency: 4,
+        ..UploadDirOptions::default()
+    };
+    let start = Instant::now();
+    let report = upload_dir(&make_config(&server), dir.path(), &options)
+        .await
+        .unwrap();
+    assert_eq!(report.new_images().count(), 4);
+    assert!(start.elapsed() < Duration::from_millis(700));
+}
+
+#[tokio::test]
+async fn missing_directory_is_an_error() {
+    let server = MockServer::start().await;
+    let result = upload_dir(
+        &make_config(&server),
+        "/definitely/not/here",
+        &UploadDirOptions::default(),
+    )
+    .await;
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
@@ -0,0 +1,121 @@
+use img_src::cdn::{UrlBuilder, UrlError};
+use img_src::models::{CdnUrls, MetadataResponse, UploadResponse};
+use img_src::models::{Fit, OutputFormat, Quality};
+
+#[test]
+fn builds_documented_example() {
+    let url = UrlBuilder::new("alice", "photos/2024/photo.webp")
+        .width(800)
+        .height(600)
+        .fit(Fit::Cover)
+        .quality(Quality::new(85).unwrap())
+        .build()
+        .unwrap();
+    assert_eq!(
+        url,
+        "https://img-src.io/i/alice/photos/2024/photo.webp?w=800&h=600&fit=cover&q=85"
+    );
+}
+
+#[test]
+fn without_options_has_no_query() {
+    let url = UrlBuilder::new("alice", "/photo.png").build().unwrap();
+    assert_eq!(url, "https://img-src.io/i/alice/photo.png");
+}
+
+#[test]
+fn encodes_path_segments() {
+    let url = UrlBuilder::new("alice", "my photos/a&b #1.jpg")
+        .fit(Fit::ScaleDown)
+        .build()
+        .unwrap();
+    assert_eq!(
+        url,
+        "https://img-src.io/i/alice/my%20photos/a&b%20%231.jpg?fit=scale-down"
+    );
+}
+
+#[test]
+fn format_replaces_or_appends_extension() {
+    let url = UrlBuilder::new("alice", "a/photo.final.jpg")
+        .format(OutputFormat::Avif)
+        .build()
+        .unwrap();
+    assert_eq!(url, "https://img-src.io/i/alice/a/photo.final.avif");
+
+    let url = UrlBuilder::new("alice", "a/photo")
+        .format(OutputFormat::Png)
+        .build()
+        .unwrap();
+    assert_eq!(url, "https://img-src.io/i/alice/a/photo.png");
+}
+
+#[test]
+fn newer_fits_and_formats_pass_through() {
+    let url = UrlBuilder::new("alice", "photo.jpg")
+        .fit(Fit::from("crop"))
+        .format(OutputFormat::Jxl)
+        .build()
+        .unwrap();
+    assert_eq!(url, "https://img-src.io/i/alice/photo.jxl?fit=crop");
+}
+
+#[test]
+fn custom_base_url() {
+    let url = UrlBuilder::new("alice", "photo.jpg")
+        .base_url("https://cdn.example.com/")
+        .build()
+        .unwrap();
+    assert_eq!(url, "https://cdn.example.com/i/alice/photo.jpg");
+}
+
+#[test]
+fn from_upload_and_metadata_replace_query() {
+    let upload = UploadResponse {
+        url: "https://img-src.io/i/alice/photo.jpg?w=10".into(),
+        ..Default::default()
+    };
+    let url = UrlBuilder::from_upload(&upload)
+        .width(320)
+        .format(OutputFormat::Webp)
+        .build()
+        .unwrap();
+    assert_eq!(url, "https://img-src.io/i/alice/photo.webp?w=320");
+
+    let metadata = MetadataResponse {
+        urls: Box::new(CdnUrls {
+            original: "https://img-src.io/i/alice/my%20photo.jpg".into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let url = UrlBuilder::from_metadata(&metadata)
+        .format(OutputFormat::Jpeg)
+        .build()
+        .unwrap();
+    assert_eq!(url, "https://img-src.io/i/alice/my%20photo.jpeg");
+}
+
+#[test]
+fn rejects_out_of_range_options() {
+    let builder = UrlBuilder::new("alice", "photo.jpg");
+    assert_eq!(builder.clone().width(0).build(), Err(UrlError::Width(0)));
+    assert_eq!(builder.clone().height(0).build(), Err(UrlError::Height(0)));
+    let reported: Quality = serde_json::from_value(serde_json::json!(101)).unwrap();
+    assert_eq!(
+        builder.clone().quality(reported).build(),
+        Err(UrlError::Quality(101))
+    );
+    assert_eq!(
+        UrlBuilder::new("", "photo.jpg").build(),
+        Err(UrlError::EmptyPath)
+    );
+    assert_eq!(
+        UrlBuilder::new("alice", "/").build(),
+        Err(UrlError::EmptyPath)
+    );
+    assert!(matches!(
+        UrlBuilder::from_url("not a url").build(),
+        Err(UrlError::InvalidUrl(_))
+    ));
+}
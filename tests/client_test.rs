@@ -0,0 +1,46 @@
+use img_src::apis::configuration::Configuration;
+use img_src::ImgSrcClient;
+
+#[test]
+fn client_new_sets_bearer_token() {
+    let client = ImgSrcClient::new("imgsrc_test_key_123");
+    assert_eq!(
+        client.configuration().bearer_access_token,
+        Some("imgsrc_test_key_123".into())
+    );
+    assert_eq!(client.configuration().base_path, "https://api.img-src.io");
+}
+
+#[test]
+fn client_from_configuration_keeps_fields() {
+    let mut config = Configuration::new();
+    config.base_path = "http://localhost:8787".into();
+    config.bearer_access_token = Some("token".into());
+    let client = ImgSrcClient::from(config);
+    assert_eq!(client.configuration().base_path, "http://localhost:8787");
+    assert_eq!(
+        client.configuration().bearer_access_token,
+        Some("token".into())
+    );
+}
+
+#[test]
+fn client_clones_share_configuration() {
+    let client = ImgSrcClient::new("token");
+    let cloned = client.clone();
+    assert!(std::ptr::eq(client.configuration(), cloned.configuration()));
+}
+
+#[test]
+fn client_handles_are_copy() {
+    let client = ImgSrcClient::new("token");
+    let images = client.images();
+    let again = images;
+    let _ = (
+        images,
+        again,
+        client.presets(),
+        client.settings(),
+        client.usage(),
+    );
+}
@@ -0,0 +1,87 @@
+//! Helpers shared by the tests that run against a wiremock server.
+
+#![allow(dead_code)]
+
+use std::path::Path;
+use std::time::Duration;
+
+use img_src::apis::configuration::{Configuration, RetryPolicy};
+use img_src::dedup::image_id_for_hash;
+use serde_json::json;
+use wiremock::MockServer;
+
+/// Configuration for `server` with a test token and retries disabled.
+pub fn make_config(server: &MockServer) -> Configuration {
+    make_config_with(server, RetryPolicy::disabled())
+}
+
+/// Like [`make_config`], retrying according to `policy`.
+pub fn make_config_with(server: &MockServer, policy: RetryPolicy) -> Configuration {
+    let mut config = make_config_without_credentials(server);
+    config.bearer_access_token = Some("imgsrc_test".into());
+    config.retry_policy = policy;
+    config
+}
+
+/// Configuration for `server` with no credential set and retries disabled.
+pub fn make_config_without_credentials(server: &MockServer) -> Configuration {
+    let mut config = Configuration::new();
+    config.base_path = server.uri();
+    config.retry_policy = RetryPolicy::disabled();
+    config
+}
+
+/// Default retry policy without jitter and with a 1ms initial backoff.
+pub fn fast_retries() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::from_millis(1),
+        jitter: false,
+        ..RetryPolicy::default()
+    }
+}
+
+/// Writes `contents` to `relative` under `root`, creating parent directories.
+pub fn write(root: &Path, relative: &str, contents: &str) {
+    let path = root.join(relative);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+/// Response body of a successful upload of image `abc123`.
+pub fn upload_body() -> serde_json::Value {
+    json!({
+        "id": "abc123",
+        "hash": "deadbeef",
+        "url": "https://cdn.img-src.io/user/photo.webp",
+        "paths": ["user/photo.webp"],
+        "is_new": true,
+        "visibility": "public",
+        "size": 314,
+        "format": "png",
+        "available_formats": { "webp": "u.webp", "avif": "u.avif", "jpeg": "u.jpeg", "png": "u.png", "jxl": "u.jxl" },
+        "uploaded_at": "2024-01-01T00:00:00Z",
+        "_links": { "self": "/api/v1/images/abc123", "delete": "/api/v1/images/abc123" }
+    })
+}
+
+/// Like [`upload_body`], for the image with content hash `hash` published at `paths`.
+pub fn upload_body_for(hash: &str, paths: &[&str]) -> serde_json::Value {
+    let mut body = upload_body();
+    body["id"] = json!(image_id_for_hash(hash));
+    body["hash"] = json!(hash);
+    body["paths"] = json!(paths);
+    body
+}
+
+/// Entry for image `id` published at `paths`, as returned by the image listing.
+pub fn image_item(id: &str, paths: &[&str]) -> serde_json::Value {
+    json!({
+        "id": id,
+        "original_filename": format!("{id}.png"),
+        "visibility": "public",
+        "size": 1,
+        "uploaded_at": "2024-01-01T00:00:00Z",
+        "url": format!("/api/v1/images/{id}"),
+        "paths": paths
+    })
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use common::make_config_without_credentials;
+use futures_util::future::BoxFuture;
+use img_src::apis::auth::AuthError;
+use img_src::apis::configuration::{Configuration, CredentialProvider, Secret};
+use img_src::apis::credentials::{EnvToken, FileToken, StaticToken};
+use img_src::apis::{presets_api, Error};
+use img_src::ImgSrcClient;
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn config_with_provider(
+    server: &MockServer,
+    provider: impl CredentialProvider + 'static,
+) -> Configuration {
+    let mut config = make_config_without_credentials(server);
+    config.credential_provider = Some(Arc::new(provider));
+    config
+}
+
+async fn mount_token(server: &MockServer, token: &str, status: u16) {
+    let response = if status == 200 {
+        ResponseTemplate::new(200).set_body_json(json!({ "presets": [], "total": 0 }))
+    } else {
+        ResponseTemplate::new(status)
+    };
+    Mock::given(method("GET"))
+        .and(path("/api/v1/settings/presets"))
+        .and(header("authorization", format!("Bearer {token}").as_str()))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+/// Hands out `current` and switches to `next` when refreshed.
+#[derive(Debug)]
+struct Rotating {
+    current: Mutex<String>,
+    next: String,
+    refreshes: AtomicUsize,
+}
+
+impl Rotating {
+    fn new(current: &str, next: &str) -> Rotating {
+        Rotating {
+            current: Mutex::new(current.into()),
+            next: next.into(),
+            refreshes: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl CredentialProvider for Rotating {
+    fn token(&self) -> BoxFuture<'_, Result<Secret, AuthError>> {
+        let token = Secret::from(self.current.lock().unwrap().as_str());
+        Box::pin(std::future::ready(Ok(token)))
+    }
+
+    fn refresh<'a>(&'a self, rejected: &'a Secret) -> BoxFuture<'a, Result<bool, AuthError>> {
+        self.refreshes.fetch_add(1, Ordering::SeqCst);
+        let mut current = self.current.lock().unwrap();
+        *current = self.next.clone();
+        let changed = current.as_str() != rejected.expose_secret();
+        Box::pin(std::future::ready(Ok(changed)))
+    }
+}
+
+#[tokio::test]
+async fn refreshes_and_retries_once_after_401() {
+    let server = MockServer::start().await;
+    mount_token(&server, "imgsrc_old", 401).await;
+    mount_token(&server, "imgsrc_new", 200).await;
+
+    let provider = Arc::new(Rotating::new("imgsrc_old", "imgsrc_new"));
+    let config = config_with_provider(&server, provider.clone());
+    presets_api::list_presets(&config).await.unwrap();
+    presets_api::list_presets(&config).await.unwrap();
+
+    assert_eq!(provider.refreshes.load(Ordering::SeqCst), 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn gives_up_after_one_refresh() {
+    let server = MockServer::start().await;
+    mount_token(&server, "imgsrc_old", 401).await;
+    mount_token(&server, "imgsrc_new", 401).await;
+
+    let provider = Arc::new(Rotating::new("imgsrc_old", "imgsrc_new"));
+    let err = presets_api::list_presets(&config_with_provider(&server, provider.clone()))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::ResponseError(ref r) if r.status == 401));
+    assert_eq!(provider.refreshes.load(Ordering::SeqCst), 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn static_token_is_not_retried() {
+    let server = MockServer::start().await;
+    mount_token(&server, "imgsrc_revoked", 401).await;
+
+    let err = presets_api::list_presets(&config_with_provider(
+        &server,
+        StaticToken::new("imgsrc_revoked"),
+    ))
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, Error::ResponseError(ref r) if r.status == 401));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn provider_takes_precedence_over_bearer_token() {
+    let server = MockServer::start().await;
+    mount_token(&server, "imgsrc_provided", 200).await;
+
+    let client = ImgSrcClient::with_credential_provider(StaticToken::new("imgsrc_provided"));
+    let mut config = client.configuration().clone();
+    config.base_path = server.uri();
+    config.bearer_access_token = Some("imgsrc_fixed".into());
+    presets_api::list_presets(&config).await.unwrap();
+}
+
+#[tokio::test]
+async fn file_token_picks_up_rotated_key() {
+    let server = MockServer::start().await;
+    mount_token(&server, "imgsrc_old", 401).await;
+    mount_token(&server, "imgsrc_new", 200).await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("api-key");
+    std::fs::write(&file, "imgsrc_old\n").unwrap();
+    let provider = FileToken::new(&file);
+    assert_eq!(provider.token().await.unwrap(), "imgsrc_old".into());
+
+    std::fs::write(&file, "imgsrc_new\n").unwrap();
+    presets_api::list_presets(&config_with_provider(&server, provider))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn env_token_reads_variable_per_request() {
+    let server = MockServer::start().await;
+    mount_token(&server, "imgsrc_env", 200).await;
+    let config = config_with_provider(&server, EnvToken::new("IMGSRC_CREDENTIALS_TEST_KEY"));
+
+    let err = presets_api::list_presets(&config).await.unwrap_err();
+    assert!(matches!(err, Error::Auth(AuthError::Provider(_))));
+
+    std::env::set_var("IMGSRC_CREDENTIALS_TEST_KEY", "imgsrc_env");
+    presets_api::list_presets(&config).await.unwrap();
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
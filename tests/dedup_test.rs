@@ -0,0 +1,206 @@
+mod common;
+
+use common::{make_config, upload_body_for};
+use img_src::bulk::{upload_dir, UploadDirOptions, UploadOutcome};
+use img_src::dedup::{
+    image_id_for_hash, sha256_bytes, sha256_file, upload_image_if_new, HashIndex, PrecheckedUpload,
+};
+use img_src::ImgSrcClient;
+use serde_json::json;
+use std::sync::Arc;
+use wiremock::matchers::{method, path, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+#[test]
+fn hashes_and_ids() {
+    assert_eq!(sha256_bytes(b""), EMPTY_SHA256);
+    assert_eq!(image_id_for_hash(EMPTY_SHA256), "e3b0c44298fc1c14");
+    assert_eq!(image_id_for_hash("abc"), "abc");
+}
+
+#[tokio::test]
+async fn hashes_files_in_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("big.png");
+    let data = vec![7u8; 200 * 1024];
+    std::fs::write(&file, &data).unwrap();
+    assert_eq!(sha256_file(&file).await.unwrap(), sha256_bytes(&data));
+}
+
+#[test]
+fn index_matches_hash_and_path() {
+    let index = HashIndex::new().with_username("alice");
+    assert!(index.is_empty());
+    index.insert(
+        image_id_for_hash(EMPTY_SHA256),
+        None,
+        ["alice/photos/a.png"],
+    );
+    assert_eq!(index.len(), 1);
+    assert!(index.contains_hash(EMPTY_SHA256));
+    assert!(index.contains_path(EMPTY_SHA256, "/photos/a.png"));
+    assert!(index.contains_path(EMPTY_SHA256, "alice/photos/a.png"));
+    assert!(!index.contains_path(EMPTY_SHA256, "photos/b.png"));
+    assert!(!index.contains_path(EMPTY_SHA256, "a.png"));
+
+    // A known full hash must match, not just the ID prefix.
+    let other = format!("{}{}", &EMPTY_SHA256[..16], "0".repeat(48));
+    index.insert(
+        image_id_for_hash(EMPTY_SHA256),
+        Some(EMPTY_SHA256),
+        Vec::<String>::new(),
+    );
+    assert!(!index.contains_hash(&other));
+}
+
+#[test]
+fn index_does_not_match_other_folders() {
+    let index = HashIndex::new();
+    index.insert(
+        image_id_for_hash(EMPTY_SHA256),
+        Some(EMPTY_SHA256),
+        ["alice/backup/cat.png"],
+    );
+    assert!(!index.contains_path(EMPTY_SHA256, "cat.png"));
+    assert!(!index.contains_path(EMPTY_SHA256, "backup/cat.png"));
+
+    let index = HashIndex::new().with_username("alice");
+    index.insert(
+        image_id_for_hash(EMPTY_SHA256),
+        Some(EMPTY_SHA256),
+        ["alice/backup/cat.png"],
+    );
+    assert!(index.contains_path(EMPTY_SHA256, "backup/cat.png"));
+    assert!(!index.contains_path(EMPTY_SHA256, "cat.png"));
+
+    // Only the username is stripped, not any first folder.
+    let index = HashIndex::new().with_username("alice");
+    index.insert(
+        image_id_for_hash(EMPTY_SHA256),
+        Some(EMPTY_SHA256),
+        ["backup/cat.png"],
+    );
+    assert!(!index.contains_path(EMPTY_SHA256, "cat.png"));
+}
+
+#[tokio::test]
+async fn index_round_trips_through_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("index.json");
+    let index = HashIndex::new();
+    index.insert("e3b0c44298fc1c14", Some(EMPTY_SHA256), ["photos/a.png"]);
+    index.save(&file).await.unwrap();
+
+    let loaded = HashIndex::load(&file).await.unwrap();
+    assert!(loaded.contains_path(EMPTY_SHA256, "photos/a.png"));
+}
+
+#[tokio::test]
+async fn fetches_index_from_listing() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images"))
+        .and(query_param_is_missing("path"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "images": [{
+                "id": image_id_for_hash(EMPTY_SHA256),
+                "original_filename": "a.png",
+                "visibility": "public",
+                "size": 0,
+                "uploaded_at": "2024-01-01T00:00:00Z",
+                "url": "/api/v1/images/e3b0c44298fc1c14",
+                "paths": ["a.png"]
+            }],
+            "folders": [],
+            "total": 1,
+            "limit": 100,
+            "offset": 0,
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+
+    let index = HashIndex::fetch(&make_config(&server), None).await.unwrap();
+    assert!(index.contains_path(EMPTY_SHA256, "a.png"));
+}
+
+#[tokio::test]
+async fn skips_transfer_when_already_published() {
+    let server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("a.png");
+    std::fs::write(&file, b"hello").unwrap();
+    let hash = sha256_bytes(b"hello");
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(upload_body_for(&hash, &["photos/a.png"])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = make_config(&server);
+    let index = HashIndex::new();
+    let first = upload_image_if_new(&config, &index, file.clone(), Some("photos"), None)
+        .await
+        .unwrap();
+    assert!(matches!(first, PrecheckedUpload::Uploaded(_)));
+
+    let client = ImgSrcClient::from_configuration(config);
+    let second = client
+        .images()
+        .upload_if_new(&index, file, Some("/photos/"), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        second,
+        PrecheckedUpload::AlreadyUploaded {
+            id: image_id_for_hash(&hash).to_owned(),
+            hash,
+        }
+    );
+}
+
+#[tokio::test]
+async fn bulk_upload_uses_hash_index() {
+    let server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("known.png"), b"known").unwrap();
+    std::fs::write(dir.path().join("fresh.png"), b"fresh").unwrap();
+    let fresh = sha256_bytes(b"fresh");
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(upload_body_for(&fresh, &["assets/fresh.png"])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let index = Arc::new(HashIndex::new());
+    let known = sha256_bytes(b"known");
+    index.insert(
+        image_id_for_hash(&known),
+        Some(&known),
+        ["assets/known.png"],
+    );
+    let options = UploadDirOptions {
+        target_prefix: Some("assets".into()),
+        hash_index: Some(index.clone()),
+        ..UploadDirOptions::default()
+    };
+    let report = upload_dir(&make_config(&server), dir.path(), &options)
+        .await
+        .unwrap();
+    assert_eq!(report.already_uploaded().count(), 1);
+    assert_eq!(report.new_images().count(), 1);
+    assert!(matches!(
+        &report.files[1].outcome,
+        UploadOutcome::AlreadyUploaded { hash, .. } if *hash == known
+    ));
+    assert!(index.contains_path(&fresh, "assets/fresh.png"));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::make_config;
+use img_src::apis::configuration::Configuration;
+use img_src::apis::{images_api, presets_api, Error, ResponseContent};
+use img_src::cdn::{UrlBuilder, UrlError};
+use img_src::ImgSrcError;
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn response_error<T>(status: u16, content: &str) -> Error<T> {
+    Error::ResponseError(ResponseContent {
+        status: reqwest::StatusCode::from_u16(status).unwrap(),
+        headers: reqwest::header::HeaderMap::new(),
+        content: content.to_owned(),
+        entity: None,
+    })
+}
+
+/// Calls two endpoints with different error types through one `?`.
+async fn metadata_then_preset(config: &Configuration) -> Result<String, ImgSrcError> {
+    let image = images_api::get_image(config, "abc123").await?;
+    let preset = presets_api::get_preset(config, "p1").await?;
+    Ok(format!("{} {}", image.id, preset.name))
+}
+
+#[tokio::test]
+async fn endpoint_errors_convert_with_details() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images/abc123"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "error": {
+                "code": "NOT_FOUND",
+                "message": "Image not found",
+                "status": 404,
+                "path": "/api/v1/images/abc123"
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let err = metadata_then_preset(&make_config(&server))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(reqwest::StatusCode::NOT_FOUND));
+    assert_eq!(err.code(), Some("NOT_FOUND"));
+    assert_eq!(err.message(), Some("Image not found"));
+    assert_eq!(err.path(), Some("/api/v1/images/abc123"));
+    assert!(err.is_not_found());
+    assert!(!err.is_retryable());
+    assert_eq!(
+        err.to_string(),
+        "API error 404 Not Found: NOT_FOUND: Image not found"
+    );
+}
+
+#[tokio::test]
+async fn rate_limited_errors_keep_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/settings/presets"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("x-ratelimit-limit", "100")
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", "1700000060")
+                .insert_header("x-request-id", "req_429")
+                .set_body_json(json!({
+                    "error": { "code": "RATE_LIMITED", "message": "Slow down", "status": 429 }
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let err = presets_api::list_presets(&make_config(&server))
+        .await
+        .unwrap_err();
+    let Error::ResponseError(ref content) = err else {
+        panic!("expected a response error, got {err:?}");
+    };
+    let rate_limit = content.rate_limit().unwrap();
+    assert_eq!(rate_limit.limit, 100);
+    assert_eq!(rate_limit.remaining, 0);
+    assert_eq!(rate_limit.reset, Some(1700000060));
+    assert_eq!(content.request_id(), Some("req_429"));
+
+    let err = ImgSrcError::from(err);
+    assert!(err.is_rate_limited());
+    assert_eq!(err.rate_limit().map(|r| r.remaining), Some(0));
+    assert_eq!(err.request_id(), Some("req_429"));
+}
+
+#[test]
+fn status_helpers() {
+    let err = ImgSrcError::from(response_error::<()>(429, "rate limited"));
+    assert!(err.is_rate_limited());
+    assert!(err.is_retryable());
+    assert!(err.detail().is_none());
+
+    let err = ImgSrcError::from(response_error::<()>(409, "{}"));
+    assert!(err.is_conflict());
+    assert!(!err.is_retryable());
+
+    assert!(ImgSrcError::from(response_error::<()>(503, "")).is_retryable());
+    assert!(ImgSrcError::from(response_error::<()>(401, "")).is_unauthorized());
+}
+
+#[test]
+fn local_errors_convert() {
+    let err: ImgSrcError = UrlBuilder::new("alice", "a.png")
+        .width(0)
+        .build()
+        .unwrap_err()
+        .into();
+    assert!(matches!(err, ImgSrcError::Invalid(_)));
+    assert_eq!(err.status(), None);
+    let source = std::error::Error::source(&err).unwrap();
+    assert_eq!(source.downcast_ref::<UrlError>(), Some(&UrlError::Width(0)));
+
+    let err: ImgSrcError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+    assert!(matches!(err, ImgSrcError::Io(_)));
+}
@@ -0,0 +1,367 @@
+mod common;
+
+use common::{image_item, make_config};
+use img_src::bulk::BulkDeleteOptions;
+use img_src::folders::{
+    delete_folder, folder_tree, DeleteFolderError, FolderNode, FolderTreeOptions,
+};
+use img_src::ImgSrcClient;
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockBuilder, MockServer, ResponseTemplate};
+
+fn item(id: &str, size: i64) -> serde_json::Value {
+    let mut item = image_item(id, &[]);
+    item["size"] = json!(size);
+    item
+}
+
+fn listing(
+    images: Vec<serde_json::Value>,
+    folders: serde_json::Value,
+    offset: i32,
+    has_more: bool,
+) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "images": images,
+        "folders": folders,
+        "total": 3,
+        "limit": 2,
+        "offset": offset,
+        "has_more": has_more
+    }))
+}
+
+fn list(folder: Option<&str>, offset: i32) -> MockBuilder {
+    let builder = Mock::given(method("GET"))
+        .and(path("/api/v1/images"))
+        .and(query_param("offset", offset.to_string()));
+    match folder {
+        Some(folder) => builder.and(query_param("path", folder)),
+        None => builder.and(query_param_is_missing("path")),
+    }
+}
+
+async fn mount_tree(server: &MockServer) {
+    // Root: two pages, subfolders repeated on each.
+    let root_folders = json!([
+        { "name": "photos", "image_count": 3 },
+        { "name": "docs", "image_count": 0 }
+    ]);
+    list(None, 0)
+        .respond_with(listing(
+            vec![item("r1", 10), item("r2", 20)],
+            root_folders.clone(),
+            0,
+            true,
+        ))
+        .mount(server)
+        .await;
+    list(None, 2)
+        .respond_with(listing(vec![item("r3", 30)], root_folders, 2, false))
+        .mount(server)
+        .await;
+    list(Some("photos"), 0)
+        .respond_with(listing(
+            vec![item("p1", 100)],
+            json!([{ "name": "2024", "image_count": 2 }]),
+            0,
+            false,
+        ))
+        .mount(server)
+        .await;
+    list(Some("photos/2024"), 0)
+        .respond_with(listing(
+            vec![item("y1", 1000), item("y2", 2000)],
+            json!([]),
+            0,
+            false,
+        ))
+        .mount(server)
+        .await;
+    list(Some("docs"), 0)
+        .respond_with(listing(vec![], json!([]), 0, false))
+        .mount(server)
+        .await;
+}
+
+fn options() -> FolderTreeOptions {
+    FolderTreeOptions {
+        page_size: 2,
+        ..FolderTreeOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn walks_whole_tree_with_aggregates() {
+    let server = MockServer::start().await;
+    mount_tree(&server).await;
+
+    let tree = folder_tree(&make_config(&server), None, options())
+        .await
+        .unwrap();
+    assert_eq!(tree.path, "");
+    assert_eq!(tree.images.len(), 3);
+    assert_eq!(tree.own_size(), 60);
+    assert_eq!(tree.image_count(), 6);
+    assert_eq!(tree.total_size(), 3160);
+
+    let names: Vec<&str> = tree.folders.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["photos", "docs"]);
+
+    let photos = tree.find("/photos/").unwrap();
+    assert_eq!(photos.reported_image_count, Some(3));
+    assert_eq!(photos.image_count(), 3);
+    assert_eq!(photos.total_size(), 3100);
+
+    let year = tree.find("photos/2024").unwrap();
+    assert_eq!(year.name, "2024");
+    assert_eq!(year.total_size(), 3000);
+    assert!(tree.find("photos/2023").is_none());
+
+    let paths: Vec<&str> = tree.walk().map(|n| n.path.as_str()).collect();
+    assert_eq!(paths, ["", "photos", "photos/2024", "docs"]);
+}
+
+#[tokio::test]
+async fn max_depth_leaves_deeper_folders_unexpanded() {
+    let server = MockServer::start().await;
+    mount_tree(&server).await;
+
+    let client = ImgSrcClient::from_configuration(make_config(&server));
+    let options = FolderTreeOptions {
+        max_depth: Some(1),
+        ..options()
+    };
+    let tree = client.images().folder_tree(None, options).await.unwrap();
+    let year: &FolderNode = tree.find("photos/2024").unwrap();
+    assert!(!year.expanded);
+    assert!(year.images.is_empty());
+    assert_eq!(year.reported_image_count, Some(2));
+    assert!(tree.find("photos").unwrap().expanded);
+}
+
+#[tokio::test]
+async fn sequential_walk_builds_same_tree() {
+    let server = MockServer::start().await;
+    mount_tree(&server).await;
+
+    let options = FolderTreeOptions {
+        concurrency: 1,
+        ..options()
+    };
+    let tree = folder_tree(&make_config(&server), None, options)
+        .await
+        .unwrap();
+    let paths: Vec<&str> = tree.walk().map(|n| n.path.as_str()).collect();
+    assert_eq!(paths, ["", "photos", "photos/2024", "docs"]);
+    assert_eq!(tree.total_size(), 3160);
+}
+
+#[tokio::test]
+async fn starts_from_subfolder() {
+    let server = MockServer::start().await;
+    mount_tree(&server).await;
+
+    let tree = folder_tree(&make_config(&server), Some("photos"), options())
+        .await
+        .unwrap();
+    assert_eq!(tree.name, "photos");
+    assert_eq!(tree.reported_image_count, None);
+    assert_eq!(tree.image_count(), 3);
+}
+
+#[tokio::test]
+async fn propagates_listing_errors() {
+    let server = MockServer::start().await;
+    list(None, 0)
+        .respond_with(listing(
+            vec![],
+            json!([{ "name": "broken", "image_count": 1 }]),
+            0,
+            false,
+        ))
+        .mount(&server)
+        .await;
+    list(Some("broken"), 0)
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    assert!(folder_tree(&make_config(&server), None, options())
+        .await
+        .is_err());
+}
+
+async fn mount_campaigns(server: &MockServer) {
+    list(Some("campaigns/2023"), 0)
+        .respond_with(listing(
+            vec![image_item("only", &["alice/campaigns/2023/only.png"])],
+            json!([{ "name": "x", "image_count": 1 }]),
+            0,
+            false,
+        ))
+        .mount(server)
+        .await;
+    list(Some("campaigns/2023/x"), 0)
+        .respond_with(listing(
+            vec![image_item(
+                "shared",
+                &["alice/campaigns/2023/x/shared.png", "alice/keep/shared.png"],
+            )],
+            json!([]),
+            0,
+            false,
+        ))
+        .mount(server)
+        .await;
+}
+
+fn options_for_alice() -> BulkDeleteOptions {
+    BulkDeleteOptions {
+        username: Some("alice".into()),
+        ..BulkDeleteOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn delete_folder_destroys_or_unlinks_by_outside_paths() {
+    let server = MockServer::start().await;
+    mount_campaigns(&server).await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/api/v1/images/path/alice/campaigns%2F2023%2Fonly.png",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "message": "Path deleted",
+            "remaining_paths": [],
+            "image_deleted": true,
+            "deleted_at": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/api/v1/images/path/alice/campaigns%2F2023%2Fx%2Fshared.png",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "message": "Path deleted",
+            "remaining_paths": ["alice/keep/shared.png"],
+            "image_deleted": false,
+            "deleted_at": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/shared"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = ImgSrcClient::from_configuration(make_config(&server));
+    let report = client
+        .images()
+        .delete_folder("/campaigns/2023/", &options_for_alice())
+        .await
+        .unwrap();
+
+    assert_eq!(report.prefix, "campaigns/2023");
+    let destroyed: Vec<&str> = report.destroyed().map(|i| i.id.as_str()).collect();
+    assert_eq!(destroyed, ["only"]);
+    let unlinked: Vec<&str> = report.unlinked().map(|i| i.id.as_str()).collect();
+    assert_eq!(unlinked, ["shared"]);
+    assert_eq!(report.images[1].outside_paths, ["keep/shared.png"]);
+    assert!(report.is_success());
+}
+
+#[tokio::test]
+async fn delete_folder_dry_run_predicts_without_deleting() {
+    let server = MockServer::start().await;
+    mount_campaigns(&server).await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let options = BulkDeleteOptions {
+        dry_run: true,
+        ..options_for_alice()
+    };
+    let report = delete_folder(&make_config(&server), "campaigns/2023", &options)
+        .await
+        .unwrap();
+
+    assert_eq!(report.destroyed().count(), 1);
+    assert_eq!(report.unlinked().count(), 1);
+    assert_eq!(report.images[0].paths.len(), 1);
+}
+
+#[tokio::test]
+async fn delete_folder_skips_images_outside_prefix_or_without_paths() {
+    let server = MockServer::start().await;
+    // The server's prefix filter also matches the sibling `2023-old`.
+    list(Some("campaigns/2023"), 0)
+        .respond_with(listing(
+            vec![
+                image_item("only", &["alice/campaigns/2023/only.png"]),
+                image_item("old", &["alice/campaigns/2023-old/old.png"]),
+                image_item("unknown", &[]),
+            ],
+            json!([]),
+            0,
+            false,
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/api/v1/images/path/alice/campaigns%2F2023%2Fonly.png",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "message": "Path deleted",
+            "remaining_paths": [],
+            "image_deleted": true,
+            "deleted_at": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let report = delete_folder(
+        &make_config(&server),
+        "campaigns/2023",
+        &options_for_alice(),
+    )
+    .await
+    .unwrap();
+
+    let ids: Vec<&str> = report.images.iter().map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, ["only"]);
+    assert_eq!(report.skipped, ["unknown"]);
+    assert_eq!(
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.method == wiremock::http::Method::DELETE)
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn delete_folder_refuses_root() {
+    let server = MockServer::start().await;
+    let err = delete_folder(&make_config(&server), "/", &options_for_alice())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DeleteFolderError::EmptyPrefix));
+}
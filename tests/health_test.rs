@@ -0,0 +1,128 @@
+mod common;
+
+use common::{make_config, make_config_with};
+use img_src::apis::configuration::RetryPolicy;
+use img_src::apis::health_api::{self, ReadinessCheck};
+use img_src::apis::Error;
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+fn healthy() -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .set_body_json(json!({ "status": "healthy", "timestamp": "2024-01-01T00:00:00Z" }))
+}
+
+#[tokio::test]
+async fn health_check_is_unauthenticated() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let health = health_api::health_check(&make_config(&server))
+        .await
+        .unwrap();
+    assert_eq!(health.status, "healthy");
+    assert_eq!(health.timestamp, "2024-01-01T00:00:00Z");
+
+    let requests: Vec<Request> = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("authorization"));
+}
+
+#[tokio::test]
+async fn health_check_error_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+        .mount(&server)
+        .await;
+
+    match health_api::health_check(&make_config(&server)).await {
+        Err(Error::ResponseError(content)) => assert_eq!(content.status, 503),
+        other => panic!("expected a response error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn readiness_is_cached_within_ttl() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = make_config(&server);
+    let readiness = ReadinessCheck::new(Duration::from_secs(60));
+    for _ in 0..5 {
+        assert!(readiness.is_ready(&config).await);
+    }
+}
+
+#[tokio::test]
+async fn readiness_rechecks_after_ttl_or_invalidate() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let config = make_config(&server);
+    let readiness = ReadinessCheck::new(Duration::from_millis(20));
+    assert!(!readiness.is_ready(&config).await);
+    assert!(!readiness.is_ready(&config).await);
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert!(!readiness.is_ready(&config).await);
+    readiness.invalidate().await;
+    assert!(!readiness.is_ready(&config).await);
+}
+
+#[tokio::test]
+async fn readiness_check_is_bounded() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy().set_delay(Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    let config = make_config_with(&server, RetryPolicy::default());
+    let readiness =
+        ReadinessCheck::new(Duration::from_secs(60)).with_timeout(Duration::from_millis(100));
+    let started = std::time::Instant::now();
+    assert!(!readiness.is_ready(&config).await);
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn readiness_check_is_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503).insert_header("retry-after", "30"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = make_config_with(&server, RetryPolicy::default());
+    let readiness = ReadinessCheck::default();
+    assert!(!readiness.is_ready(&config).await);
+}
+
+#[test]
+fn readiness_default_ttl() {
+    assert_eq!(ReadinessCheck::default().ttl(), ReadinessCheck::DEFAULT_TTL);
+    assert_eq!(
+        ReadinessCheck::default().timeout(),
+        ReadinessCheck::DEFAULT_TIMEOUT
+    );
+}
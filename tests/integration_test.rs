@@ -10,8 +10,8 @@
 use img_src::apis::configuration::Configuration;
 use img_src::apis::{images_api, presets_api, settings_api, usage_api};
 use img_src::models::{
-    CreatePresetRequest, CreateSignedUrlRequest, UpdatePresetRequest, UpdateSettingsRequest,
-    UpdateVisibilityRequest,
+    CreatePresetRequest, CreateSignedUrlRequest, Fit, PresetParams, Quality, UpdatePresetRequest,
+    UpdateSettingsRequest, UpdateVisibilityRequest, Visibility,
 };
 use std::io::Write;
 
@@ -47,7 +47,7 @@ fn make_config() -> Configuration {
     let api_key =
         std::env::var("IMGSRC_API_KEY").expect("IMGSRC_API_KEY environment variable must be set");
     let mut config = Configuration::new();
-    config.bearer_access_token = Some(api_key);
+    config.bearer_access_token = Some(api_key.into());
     if let Ok(server_url) = std::env::var("IMGSRC_SERVER_URL") {
         config.base_path = server_url;
     }
@@ -106,7 +106,6 @@ async fn full_integration() {
         .await
         .expect("get_settings failed");
     let settings = &settings_resp.settings;
-    let username = settings.username.clone();
     let plan = settings.plan.clone();
     let original_quality = settings.default_quality;
     println!("[PASS] get_settings: plan={plan}");
@@ -126,14 +125,18 @@ async fn full_integration() {
     // ── 3. upload_image ──────────────────────────────────────────────
     let (_tmp_file, tmp_path) = write_temp_png();
     let target_path = "__sdk_test".to_string();
-    let upload =
-        images_api::upload_image(&config, Some(tmp_path), Some(&target_path), Some("public"))
-            .await
-            .expect("upload_image failed");
+    let upload = images_api::upload_image(
+        &config,
+        Some(tmp_path),
+        Some(&target_path),
+        Some(Visibility::Public),
+    )
+    .await
+    .expect("upload_image failed");
     let image_id = upload.id.clone();
     cleanup.image_id = Some(image_id.clone());
     assert!(!image_id.is_empty(), "image ID should not be empty");
-    assert_eq!(upload.visibility, "public");
+    assert_eq!(upload.visibility, Visibility::Public);
     println!("[PASS] upload_image: id={image_id}, size={}", upload.size);
 
     // ── 4. list_images ───────────────────────────────────────────────
@@ -191,7 +194,7 @@ async fn full_integration() {
         .await
         .expect("get_image failed");
     assert_eq!(meta.id, image_id);
-    assert_eq!(meta.visibility, "public");
+    assert_eq!(meta.visibility, Visibility::Public);
     println!(
         "[PASS] get_image: id={}, visibility={}, hash={}",
         meta.id, meta.visibility, meta.metadata.hash
@@ -202,22 +205,22 @@ async fn full_integration() {
         let vis_resp = images_api::update_visibility(
             &config,
             &image_id,
-            UpdateVisibilityRequest::new("private".to_string()),
+            UpdateVisibilityRequest::new(Visibility::Private),
         )
         .await
         .expect("update_visibility (to private) failed");
-        assert_eq!(vis_resp.visibility, "private");
+        assert_eq!(vis_resp.visibility, Visibility::Private);
         println!("[PASS] update_visibility: → private");
 
         // ── 8. update_visibility → public ────────────────────────────────
         let vis_resp = images_api::update_visibility(
             &config,
             &image_id,
-            UpdateVisibilityRequest::new("public".to_string()),
+            UpdateVisibilityRequest::new(Visibility::Public),
         )
         .await
         .expect("update_visibility (to public) failed");
-        assert_eq!(vis_resp.visibility, "public");
+        assert_eq!(vis_resp.visibility, Visibility::Public);
         println!("[PASS] update_visibility: → public");
     } else {
         println!("[SKIP] update_visibility: requires Pro plan");
@@ -230,6 +233,7 @@ async fn full_integration() {
             &image_id,
             Some(CreateSignedUrlRequest {
                 expires_in_seconds: Some(300),
+                transformation: None,
             }),
         )
         .await
@@ -257,10 +261,12 @@ async fn full_integration() {
         println!("[PASS] list_presets: total={}", presets_before.total);
 
         // 11. create_preset
-        let mut params = std::collections::HashMap::new();
-        params.insert("w".to_string(), serde_json::json!(200));
-        params.insert("h".to_string(), serde_json::json!(200));
-        params.insert("fit".to_string(), serde_json::json!("cover"));
+        let params = PresetParams {
+            width: Some(200),
+            height: Some(200),
+            fit: Some(Fit::Cover),
+            ..PresetParams::new()
+        };
         let preset = presets_api::create_preset(
             &config,
             Some(CreatePresetRequest {
@@ -327,7 +333,7 @@ async fn full_integration() {
     );
 
     // ── 16. update_settings ──────────────────────────────────────────
-    let new_quality = if original_quality == 85 { 80 } else { 85 };
+    let new_quality = Quality::new(if original_quality.get() == 85 { 80 } else { 85 }).unwrap();
     let updated_settings = settings_api::update_settings(
         &config,
         Some(UpdateSettingsRequest {
@@ -57,16 +57,38 @@ fn credits_large_values() {
 
 #[test]
 fn update_visibility_request_serde_round_trip() {
-    let m = UpdateVisibilityRequest::new("private".into());
+    let m = UpdateVisibilityRequest::new(Visibility::Private);
     round_trip(&m);
-    assert_eq!(m.visibility, "private");
+    assert_eq!(m.visibility, Visibility::Private);
+}
+
+#[test]
+fn visibility_serializes_as_plain_string() {
+    assert_eq!(
+        serde_json::to_value(Visibility::Private).unwrap(),
+        json!("private")
+    );
+    let v: Visibility = serde_json::from_value(json!("public")).unwrap();
+    assert_eq!(v, Visibility::Public);
+    assert_eq!(v.to_string(), "public");
+}
+
+#[test]
+fn visibility_keeps_unknown_values() {
+    let v: Visibility = serde_json::from_value(json!("unlisted")).unwrap();
+    assert_eq!(v, Visibility::Unknown("unlisted".into()));
+    assert_eq!(serde_json::to_value(&v).unwrap(), json!("unlisted"));
+    assert_eq!(
+        "private".parse::<Visibility>().unwrap(),
+        Visibility::Private
+    );
 }
 
 #[test]
 fn update_visibility_response_serde_round_trip() {
     let m = UpdateVisibilityResponse::new(
         "abc123".into(),
-        "public".into(),
+        Visibility::Public,
         "Visibility updated".into(),
     );
     round_trip(&m);
@@ -92,7 +114,7 @@ fn upload_response_has_visibility_and_i64_size() {
         "_links": { "self": "/api/v1/images/abc123", "delete": "/api/v1/images/abc123" }
     });
     let m: UploadResponse = serde_json::from_value(json).unwrap();
-    assert_eq!(m.visibility, "public");
+    assert_eq!(m.visibility, Visibility::Public);
     assert_eq!(m.size, 5_368_709_120);
     assert_eq!(m.is_new, Some(true));
 }
@@ -111,7 +133,7 @@ fn upload_response_constructor() {
         "2024-01-01".into(),
         HateoasLinks::new("/self".into(), "/del".into()),
     );
-    assert_eq!(m.visibility, "public");
+    assert_eq!(m.visibility, Visibility::Public);
     assert_eq!(m.size, 1024);
     assert!(m.is_new.is_none());
     assert!(m.dimensions.is_none());
@@ -136,7 +158,7 @@ fn image_list_item_has_visibility_and_active_signed_url() {
         }
     });
     let m: ImageListItem = serde_json::from_value(json).unwrap();
-    assert_eq!(m.visibility, "private");
+    assert_eq!(m.visibility, Visibility::Private);
     assert_eq!(m.size, 2_147_483_648);
     let signed = m.active_signed_url.as_ref().unwrap();
     assert_eq!(signed.expires_at, 1700000000);
@@ -170,7 +192,7 @@ fn image_list_item_constructor() {
         "/api".into(),
         vec!["p".into()],
     );
-    assert_eq!(m.visibility, "public");
+    assert_eq!(m.visibility, Visibility::Public);
     assert!(m.active_signed_url.is_none());
     round_trip(&m);
 }
@@ -187,7 +209,7 @@ fn search_result_has_visibility_and_i64_size() {
         "url": "/api/v1/images/s1"
     });
     let m: SearchResult = serde_json::from_value(json).unwrap();
-    assert_eq!(m.visibility, "public");
+    assert_eq!(m.visibility, Visibility::Public);
     assert_eq!(m.size, 3_000_000_000);
 }
 
@@ -202,7 +224,7 @@ fn search_result_constructor() {
         "2024-01-01".into(),
         "/api".into(),
     );
-    assert_eq!(m.visibility, "private");
+    assert_eq!(m.visibility, Visibility::Private);
     assert_eq!(m.size, 4096);
     round_trip(&m);
 }
@@ -225,7 +247,7 @@ fn metadata_response_has_visibility() {
         "_links": { "self": "/s", "delete": "/d" }
     });
     let m: MetadataResponse = serde_json::from_value(json).unwrap();
-    assert_eq!(m.visibility, "private");
+    assert_eq!(m.visibility, Visibility::Private);
     assert_eq!(m.metadata.size, 5_000_000_000);
 }
 
@@ -245,7 +267,7 @@ fn metadata_response_constructor() {
         ),
         HateoasLinks::new("/s".into(), "/d".into()),
     );
-    assert_eq!(m.visibility, "public");
+    assert_eq!(m.visibility, Visibility::Public);
     round_trip(&m);
 }
 
@@ -278,6 +300,27 @@ fn user_settings_has_plan_and_i64_timestamps() {
     assert_eq!(m.default_max_height, None);
 }
 
+#[test]
+fn user_settings_reads_out_of_range_quality() {
+    let json = json!({
+        "id": "user_123",
+        "username": "john",
+        "plan": "free",
+        "delivery_formats": ["webp"],
+        "default_quality": 0,
+        "default_fit_mode": "cover",
+        "theme": "dark",
+        "language": "en",
+        "created_at": 1700000000_i64,
+        "updated_at": 1700100000_i64,
+        "total_uploads": 0,
+        "storage_used_bytes": 0
+    });
+    let m: UserSettings = serde_json::from_value(json).unwrap();
+    assert_eq!(m.default_quality.get(), 0);
+    assert!(!m.default_quality.is_valid());
+}
+
 #[test]
 fn user_settings_constructor() {
     let m = UserSettings::new(
@@ -285,7 +328,7 @@ fn user_settings_constructor() {
         "user".into(),
         "free".into(),
         vec!["webp".into()],
-        80,
+        Quality::new(80).unwrap(),
         "cover".into(),
         "light".into(),
         "en".into(),
@@ -516,8 +559,10 @@ fn preset_with_description() {
 
 #[test]
 fn preset_constructor_no_description() {
-    let mut params = HashMap::new();
-    params.insert("w".into(), json!(800));
+    let params = PresetParams {
+        width: Some(800),
+        ..PresetParams::new()
+    };
     let m = Preset::new(
         "id".into(),
         "thumb".into(),
@@ -531,17 +576,108 @@ fn preset_constructor_no_description() {
 }
 
 #[test]
-fn create_signed_url_request_no_transformation_field() {
+fn create_signed_url_request_omits_unset_transformation() {
     let m = CreateSignedUrlRequest::new();
     let json = serde_json::to_value(&m).unwrap();
     let obj = json.as_object().unwrap();
     assert!(
         !obj.contains_key("transformation"),
-        "transformation field should not exist"
+        "unset transformation should not be serialized"
     );
     assert!(m.expires_in_seconds.is_none());
 }
 
+#[test]
+fn create_signed_url_request_with_transformation() {
+    let mut transformation = CreateSignedUrlRequestTransformation::new();
+    transformation.width = Some(200);
+    transformation.height = Some(200);
+    transformation.fit = Some(create_signed_url_request_transformation::Fit::ScaleDown);
+    transformation.quality = Quality::new(80);
+    transformation.format = Some(create_signed_url_request_transformation::Format::Avif);
+    let mut m = CreateSignedUrlRequest::new();
+    m.expires_in_seconds = Some(600);
+    m.transformation = Some(Box::new(transformation));
+
+    assert_eq!(
+        serde_json::to_value(&m).unwrap(),
+        json!({
+            "expires_in_seconds": 600,
+            "transformation": {
+                "width": 200,
+                "height": 200,
+                "fit": "scale-down",
+                "quality": 80,
+                "format": "avif"
+            }
+        })
+    );
+    round_trip(&m);
+}
+
+#[test]
+fn create_signed_url_request_transformation_keeps_unknown_fit() {
+    let json = json!({ "fit": "stretch" });
+    let m: CreateSignedUrlRequestTransformation = serde_json::from_value(json).unwrap();
+    assert_eq!(m.fit, Some(Fit::Unknown("stretch".into())));
+    round_trip(&m);
+}
+
+#[test]
+fn create_signed_url_request_transformation_keeps_out_of_range_quality() {
+    let json = json!({ "quality": 101 });
+    let m: CreateSignedUrlRequestTransformation = serde_json::from_value(json.clone()).unwrap();
+    assert!(!m.quality.unwrap().is_valid());
+    assert_eq!(serde_json::to_value(&m).unwrap(), json);
+}
+
+#[test]
+fn fit_and_output_format_use_wire_names() {
+    assert_eq!(
+        serde_json::to_value(Fit::ScaleDown).unwrap(),
+        json!("scale-down")
+    );
+    assert_eq!(
+        serde_json::to_value(OutputFormat::Jxl).unwrap(),
+        json!("jxl")
+    );
+    let f: OutputFormat = serde_json::from_value(json!("heic")).unwrap();
+    assert_eq!(f, OutputFormat::Unknown("heic".into()));
+    assert_eq!(f.to_string(), "heic");
+}
+
+#[test]
+fn quality_is_bounded() {
+    assert_eq!(Quality::new(0), None);
+    assert_eq!(Quality::new(101), None);
+    assert_eq!(Quality::new(100), Some(Quality::MAX));
+    assert!(Quality::try_from(-1_i32).is_err());
+    assert_eq!(serde_json::to_value(Quality::MIN).unwrap(), json!(1));
+    assert!(Quality::MAX.is_valid());
+}
+
+#[test]
+fn quality_from_response_round_trips() {
+    let q: Quality = serde_json::from_value(json!(300)).unwrap();
+    assert_eq!(q.get(), 300);
+    assert!(!q.is_valid());
+    assert_eq!(serde_json::to_value(q).unwrap(), json!(300));
+    assert!(serde_json::from_value::<Quality>(json!(1_i64 << 40)).is_err());
+}
+
+#[test]
+fn available_formats_lookup_by_output_format() {
+    let m = AvailableFormats::new(
+        "u.webp".into(),
+        "u.avif".into(),
+        "u.jpeg".into(),
+        "u.png".into(),
+        "u.jxl".into(),
+    );
+    assert_eq!(m.get(&OutputFormat::Avif), Some("u.avif"));
+    assert_eq!(m.get(&OutputFormat::Unknown("heic".into())), None);
+}
+
 #[test]
 fn create_signed_url_request_with_expiry() {
     let json = json!({ "expires_in_seconds": 7200 });
@@ -718,14 +854,119 @@ fn delete_preset_response_serde() {
 
 #[test]
 fn create_preset_request_serde() {
-    let mut params = HashMap::new();
-    params.insert("w".into(), json!(800));
-    params.insert("q".into(), json!(85));
+    let params = PresetParams {
+        width: Some(800),
+        quality: Quality::new(85),
+        ..PresetParams::new()
+    };
     let m = CreatePresetRequest::new("thumb".into(), params);
     assert!(m.description.is_none());
+    assert_eq!(
+        serde_json::to_value(&m).unwrap(),
+        json!({ "name": "thumb", "params": { "w": 800, "q": 85 } })
+    );
     round_trip(&m);
 }
 
+#[test]
+fn preset_params_keep_unknown_keys() {
+    let json = json!({ "w": 300, "blur": 5, "fit": "crop" });
+    let m: PresetParams = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(m.width, Some(300));
+    assert_eq!(m.fit, Some(Fit::Unknown("crop".into())));
+    assert_eq!(m.extra["blur"], 5);
+    assert_eq!(serde_json::to_value(&m).unwrap(), json);
+    assert_eq!(m.validate(), Ok(()));
+}
+
+#[test]
+fn preset_params_convert_to_and_from_wire_map() {
+    let mut map = HashMap::new();
+    map.insert("h".to_string(), json!(120));
+    map.insert("format".to_string(), json!("avif"));
+    let m = PresetParams::from(map.clone());
+    assert_eq!(m.height, Some(120));
+    assert_eq!(m.format, Some(OutputFormat::Avif));
+    assert_eq!(HashMap::from(m), map);
+
+    map.insert("q".to_string(), json!("high"));
+    let m = PresetParams::from(map.clone());
+    assert_eq!(m.quality, None);
+    assert_eq!(m.extra["q"], "high");
+    assert_eq!(HashMap::from(m), map);
+}
+
+#[test]
+fn preset_params_keep_unparsable_values() {
+    let json = json!({ "w": 1.5, "h": -3, "q": 80 });
+    let m: PresetParams = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(m.width, None);
+    assert_eq!(m.height, None);
+    assert_eq!(m.quality, Quality::new(80));
+    assert_eq!(m.extra["w"], 1.5);
+    assert_eq!(serde_json::to_value(&m).unwrap(), json);
+    assert_eq!(
+        m.validate(),
+        Err(preset_params::PresetParamsError::InvalidValue("h".into()))
+    );
+
+    let list: ListPresetsResponse = serde_json::from_value(json!({
+        "presets": [{
+            "id": "p1",
+            "name": "odd",
+            "params": { "w": 20.5 },
+            "created_at": 1700000000_i64,
+            "updated_at": 1700000000_i64,
+            "usage_count": 0
+        }],
+        "total": 1
+    }))
+    .unwrap();
+    assert_eq!(list.presets[0].params.extra["w"], 20.5);
+}
+
+#[test]
+fn preset_params_validation() {
+    let m = PresetParams {
+        width: Some(0),
+        ..PresetParams::new()
+    };
+    assert_eq!(
+        m.validate(),
+        Err(preset_params::PresetParamsError::Width(0))
+    );
+
+    let m: PresetParams = serde_json::from_value(json!({ "width": 300 })).unwrap();
+    assert_eq!(
+        m.validate(),
+        Err(preset_params::PresetParamsError::MisspelledKey {
+            key: "width".into(),
+            expected: "w"
+        })
+    );
+
+    let mut m = PresetParams {
+        width: Some(300),
+        ..PresetParams::new()
+    };
+    m.extra.insert("w".into(), json!(400));
+    assert_eq!(
+        m.validate(),
+        Err(preset_params::PresetParamsError::DuplicateKey("w".into()))
+    );
+
+    let m: PresetParams = serde_json::from_value(json!({ "q": 150 })).unwrap();
+    assert_eq!(
+        m.validate(),
+        Err(preset_params::PresetParamsError::Quality(150))
+    );
+
+    assert_eq!(
+        PresetParams::new().validate(),
+        Err(preset_params::PresetParamsError::Empty)
+    );
+}
+
 #[test]
 fn update_preset_request_serde() {
     let m = UpdatePresetRequest::new();
@@ -752,7 +993,7 @@ fn update_settings_request_with_values() {
     });
     let m: UpdateSettingsRequest = serde_json::from_value(json).unwrap();
     assert_eq!(m.delivery_formats.as_ref().unwrap().len(), 2);
-    assert_eq!(m.default_quality, Some(90));
+    assert_eq!(m.default_quality, Quality::new(90));
     assert_eq!(m.theme, Some("dark".into()));
 }
 
@@ -763,7 +1004,7 @@ fn settings_response_serde() {
         "user".into(),
         "free".into(),
         vec!["webp".into()],
-        80,
+        Quality::new(80).unwrap(),
         "cover".into(),
         "light".into(),
         "en".into(),
@@ -783,7 +1024,7 @@ fn settings_update_response_serde() {
         "user".into(),
         "pro".into(),
         vec!["webp".into()],
-        85,
+        Quality::new(85).unwrap(),
         "contain".into(),
         "dark".into(),
         "ko".into(),
@@ -872,7 +1113,7 @@ fn models_implement_clone() {
         "u".into(),
         "free".into(),
         vec![],
-        80,
+        Quality::new(80).unwrap(),
         "cover".into(),
         "light".into(),
         "en".into(),
@@ -938,7 +1179,7 @@ fn optional_fields_omitted_when_none() {
 
 #[test]
 fn preset_description_serialized_as_null_when_none() {
-    let m = Preset::new("id".into(), "name".into(), HashMap::new(), 0, 0, 0);
+    let m = Preset::new("id".into(), "name".into(), PresetParams::new(), 0, 0, 0);
     let json = serde_json::to_value(&m).unwrap();
     let obj = json.as_object().unwrap();
     assert!(
@@ -1035,7 +1276,7 @@ fn realistic_upload_response_full() {
     assert_eq!(m.id, "img_abc123def456");
     assert_eq!(m.paths.len(), 2);
     assert_eq!(m.is_new, Some(false));
-    assert_eq!(m.visibility, "public");
+    assert_eq!(m.visibility, Visibility::Public);
     assert_eq!(m.size, 2_457_600);
     let dims = m.dimensions.as_ref().unwrap();
     assert_eq!(dims.width, 3840);
@@ -1171,9 +1412,9 @@ fn realistic_image_list_response_with_signed_urls() {
     });
     let m: ImageListResponse = serde_json::from_value(json).unwrap();
     assert_eq!(m.images.len(), 2);
-    assert_eq!(m.images[0].visibility, "public");
+    assert_eq!(m.images[0].visibility, Visibility::Public);
     assert!(m.images[0].active_signed_url.is_none());
-    assert_eq!(m.images[1].visibility, "private");
+    assert_eq!(m.images[1].visibility, Visibility::Private);
     let signed = m.images[1].active_signed_url.as_ref().unwrap();
     assert_eq!(signed.expires_at, 1700100000);
     assert_eq!(m.folders.len(), 2);
@@ -1206,9 +1447,12 @@ fn realistic_preset_with_complex_params() {
         m.description,
         Some("High-definition thumbnail with cover crop and quality 90".into())
     );
-    assert_eq!(m.params.len(), 5);
-    assert_eq!(m.params["w"], 400);
-    assert_eq!(m.params["fit"], "cover");
+    assert_eq!(m.params.width, Some(400));
+    assert_eq!(m.params.height, Some(300));
+    assert_eq!(m.params.fit, Some(Fit::Cover));
+    assert_eq!(m.params.quality, Quality::new(90));
+    assert_eq!(m.params.format, Some(OutputFormat::Webp));
+    assert!(m.params.extra.is_empty());
     assert_eq!(m.usage_count, 15782);
     round_trip(&m);
 }
@@ -1243,7 +1487,7 @@ fn realistic_metadata_response() {
     });
     let m: MetadataResponse = serde_json::from_value(json).unwrap();
     assert_eq!(m.id, "img_meta123");
-    assert_eq!(m.visibility, "public");
+    assert_eq!(m.visibility, Visibility::Public);
     assert_eq!(m.metadata.width, Some(4032));
     assert_eq!(m.metadata.height, Some(3024));
     assert_eq!(m.metadata.dominant_color, Some("ff7733".into()));
@@ -1278,7 +1522,7 @@ fn realistic_settings_response() {
     assert_eq!(m.settings.plan, "pro");
     assert_eq!(m.settings.email, Some("john@example.com".into()));
     assert_eq!(m.settings.delivery_formats.len(), 3);
-    assert_eq!(m.settings.default_quality, 85);
+    assert_eq!(m.settings.default_quality.get(), 85);
     assert_eq!(m.settings.default_max_width, Some(3840));
     assert_eq!(m.settings.default_max_height, Some(2160));
     assert_eq!(m.settings.storage_used_bytes, 10_737_418_240);
@@ -1298,7 +1542,7 @@ fn user_settings_quality_boundary_values() {
         "created_at": 0, "updated_at": 0, "total_uploads": 0, "storage_used_bytes": 0
     });
     let m: UserSettings = serde_json::from_value(min_json).unwrap();
-    assert_eq!(m.default_quality, 1);
+    assert_eq!(m.default_quality, Quality::MIN);
 
     let max_json = json!({
         "id": "u2", "username": "u", "plan": "free",
@@ -1307,7 +1551,9 @@ fn user_settings_quality_boundary_values() {
         "created_at": 0, "updated_at": 0, "total_uploads": 0, "storage_used_bytes": 0
     });
     let m: UserSettings = serde_json::from_value(max_json).unwrap();
-    assert_eq!(m.default_quality, 100);
+    assert_eq!(m.default_quality, Quality::MAX);
+    assert_eq!(m.delivery_formats, [OutputFormat::Webp]);
+    assert_eq!(m.default_fit_mode, Fit::Cover);
 }
 
 #[test]
@@ -1381,3 +1627,77 @@ fn search_response_missing_query_fails() {
     let result = serde_json::from_value::<SearchResponse>(json);
     assert!(result.is_err(), "missing 'query' should fail");
 }
+
+// ============================================================
+// API key models
+// ============================================================
+
+#[test]
+fn create_api_key_request_serializes_scopes() {
+    let mut m = CreateApiKeyRequest::new("ci".into());
+    assert_eq!(serde_json::to_value(&m).unwrap(), json!({ "name": "ci" }));
+
+    m.scopes = Some(vec![
+        create_api_key_request::Scopes::Read,
+        create_api_key_request::Scopes::Write,
+    ]);
+    m.expires_in_days = Some(30);
+    assert_eq!(
+        serde_json::to_value(&m).unwrap(),
+        json!({ "name": "ci", "scopes": ["read", "write"], "expires_in_days": 30 })
+    );
+    round_trip(&m);
+}
+
+#[test]
+fn create_api_key_response_deserialize_from_json() {
+    let json = json!({
+        "id": "7f0c4a9e-1b2c-4d3e-8f9a-0b1c2d3e4f5a",
+        "key": "imgsrc_live_abcdefghijklmnop",
+        "key_prefix": "imgsrc_live_ab",
+        "name": "ci",
+        "scopes": "read,write",
+        "created_at": 1700000000_i64
+    });
+    let m: CreateApiKeyResponse = serde_json::from_value(json).unwrap();
+    assert_eq!(m.key.expose_secret(), "imgsrc_live_abcdefghijklmnop");
+    assert!(!format!("{m:?}").contains("abcdefghijklmnop"));
+    assert_eq!(m.expires_at, None);
+    round_trip(&m);
+}
+
+#[test]
+fn api_key_list_response_deserialize_from_json() {
+    let json = json!({
+        "api_keys": [{
+            "id": "7f0c4a9e-1b2c-4d3e-8f9a-0b1c2d3e4f5a",
+            "name": "ci",
+            "key_prefix": "imgsrc_live_ab",
+            "scopes": "read",
+            "created_at": 1700000000_i64,
+            "last_used_at": 1700000500_i64,
+            "total_requests": 42
+        }],
+        "total": 1
+    });
+    let m: ApiKeyListResponse = serde_json::from_value(json).unwrap();
+    assert_eq!(m.total, 1);
+    assert_eq!(m.api_keys[0].last_used_at, Some(1700000500));
+    assert_eq!(m.api_keys[0].expires_at, None);
+    assert_eq!(m.api_keys[0].total_requests, 42);
+    round_trip(&m);
+}
+
+#[test]
+fn delete_api_key_response_serde_round_trip() {
+    let m = DeleteApiKeyResponse::new(true, "API key deleted".into());
+    round_trip(&m);
+}
+
+#[test]
+fn health_response_deserialize_from_json() {
+    let json = json!({ "status": "healthy", "timestamp": "2024-01-01T00:00:00Z" });
+    let m: HealthResponse = serde_json::from_value(json).unwrap();
+    assert_eq!(m.status, "healthy");
+    round_trip(&m);
+}
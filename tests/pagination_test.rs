@@ -0,0 +1,129 @@
+mod common;
+
+use common::{image_item, make_config};
+use futures_util::{StreamExt, TryStreamExt};
+use img_src::apis::Error;
+use img_src::pagination::{list_images_stream, ListImagesOptions};
+use img_src::ImgSrcClient;
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn page(ids: &[&str], total: i32, limit: i32, offset: i32, has_more: bool) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "images": ids
+            .iter()
+            .map(|id| image_item(id, &[&format!("photos/{id}.png")]))
+            .collect::<Vec<_>>(),
+        "folders": [],
+        "total": total,
+        "limit": limit,
+        "offset": offset,
+        "has_more": has_more
+    }))
+}
+
+async fn mount_page(server: &MockServer, offset: i32, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images"))
+        .and(query_param("offset", offset.to_string()))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn streams_all_pages_and_dedupes_shifted_items() {
+    let server = MockServer::start().await;
+    mount_page(&server, 0, page(&["a", "b"], 5, 2, 0, true)).await;
+    // "b" shifted onto the second page after an insert.
+    mount_page(&server, 2, page(&["b", "c"], 5, 2, 2, true)).await;
+    mount_page(&server, 4, page(&["d"], 5, 2, 4, false)).await;
+
+    let config = make_config(&server);
+    let options = ListImagesOptions {
+        page_size: 2,
+        prefetch: 0,
+    };
+    let ids: Vec<String> = list_images_stream(&config, Some("photos"), options)
+        .map_ok(|image| image.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(ids, ["a", "b", "c", "d"]);
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    assert!(requests
+        .iter()
+        .all(|r| r.url.query().unwrap().contains("limit=2")
+            && r.url.query().unwrap().contains("path=photos")));
+}
+
+#[tokio::test]
+async fn fetches_pages_lazily() {
+    let server = MockServer::start().await;
+    mount_page(&server, 0, page(&["a", "b"], 4, 2, 0, true)).await;
+    mount_page(&server, 2, page(&["c", "d"], 4, 2, 2, false)).await;
+
+    let config = make_config(&server);
+    let options = ListImagesOptions {
+        page_size: 2,
+        prefetch: 0,
+    };
+    let first: Vec<_> = list_images_stream(&config, None, options)
+        .take(1)
+        .collect()
+        .await;
+    assert_eq!(first.len(), 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn prefetch_stays_within_total() {
+    let server = MockServer::start().await;
+    mount_page(&server, 0, page(&["a"], 3, 1, 0, true)).await;
+    mount_page(&server, 1, page(&["b"], 3, 1, 1, true)).await;
+    mount_page(&server, 2, page(&["c"], 3, 1, 2, false)).await;
+
+    let client = ImgSrcClient::from_configuration(make_config(&server));
+    let options = ListImagesOptions {
+        page_size: 1,
+        prefetch: 4,
+    };
+    let images: Vec<_> = client
+        .images()
+        .list_stream_with(None, options)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(images.len(), 3);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn ends_after_error() {
+    let server = MockServer::start().await;
+    mount_page(&server, 0, page(&["a"], 10, 1, 0, true)).await;
+    mount_page(&server, 1, ResponseTemplate::new(500)).await;
+
+    let config = make_config(&server);
+    let options = ListImagesOptions {
+        page_size: 1,
+        prefetch: 0,
+    };
+    let results: Vec<_> = list_images_stream(&config, None, options).collect().await;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap().id, "a");
+    assert!(matches!(results[1], Err(Error::ResponseError(_))));
+}
+
+#[tokio::test]
+async fn empty_listing() {
+    let server = MockServer::start().await;
+    mount_page(&server, 0, page(&[], 0, 50, 0, false)).await;
+
+    let client = ImgSrcClient::from_configuration(make_config(&server));
+    let images: Vec<_> = client.images().list_stream(None).collect().await;
+    assert!(images.is_empty());
+}
@@ -0,0 +1,259 @@
+mod common;
+
+use common::make_config;
+use img_src::models::preset_params::PresetParamsError;
+use img_src::models::Fit;
+use img_src::preset_sync::{
+    apply_presets, plan_presets, ConfigFormat, PresetChange, PresetConfig, PresetConfigError,
+    PresetSyncOptions,
+};
+use img_src::ImgSrcClient;
+use serde_json::json;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn preset(
+    id: &str,
+    name: &str,
+    description: Option<&str>,
+    params: serde_json::Value,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "description": description,
+        "params": params,
+        "created_at": 1700000000_i64,
+        "updated_at": 1700000000_i64,
+        "usage_count": 0
+    })
+}
+
+const DESIRED: &str = r#"{
+    "presets": {
+        "banner": { "params": { "w": 1200, "h": 400, "fit": "cover" } },
+        "thumbnail": { "description": "Square thumbnail", "params": { "w": 200, "h": 200, "q": 80 } },
+        "hero": { "params": { "w": 1920, "format": "avif" } }
+    }
+}"#;
+
+async fn mount_existing(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/v1/settings/presets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "presets": [
+                preset("p1", "banner", Some("Wide banner"), json!({ "w": 1200, "h": 400, "fit": "cover" })),
+                preset("p2", "thumbnail", None, json!({ "w": 150, "h": 150, "q": 80 })),
+                preset("p3", "legacy", None, json!({ "w": 64 }))
+            ],
+            "total": 3
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn plans_creates_updates_and_optional_deletes() {
+    let server = MockServer::start().await;
+    mount_existing(&server).await;
+    let desired = PresetConfig::parse(DESIRED, ConfigFormat::Json).unwrap();
+    let config = make_config(&server);
+
+    let plan = plan_presets(&config, &desired, &PresetSyncOptions::default())
+        .await
+        .unwrap();
+    let names: Vec<&str> = plan.changes.iter().map(PresetChange::name).collect();
+    assert_eq!(names, ["banner", "hero", "thumbnail"]);
+    assert!(matches!(plan.changes[0], PresetChange::Unchanged { .. }));
+    assert!(matches!(plan.changes[1], PresetChange::Create { .. }));
+    match &plan.changes[2] {
+        PresetChange::Update {
+            id,
+            description,
+            params,
+            ..
+        } => {
+            assert_eq!(id, "p2");
+            assert_eq!(description.as_deref(), Some("Square thumbnail"));
+            assert_eq!(params.as_ref().unwrap().width, Some(200));
+        }
+        other => panic!("expected update, got {other:?}"),
+    }
+    assert!(plan.has_changes());
+
+    let options = PresetSyncOptions {
+        delete_missing: true,
+    };
+    let plan = plan_presets(&config, &desired, &options).await.unwrap();
+    let names: Vec<&str> = plan.changes.iter().map(PresetChange::name).collect();
+    assert_eq!(names, ["banner", "hero", "legacy", "thumbnail"]);
+    assert_eq!(
+        plan.to_string(),
+        "+ hero {\"w\":1920,\"format\":\"avif\"}\n\
+         - legacy\n\
+         ~ thumbnail params={\"w\":200,\"h\":200,\"q\":80} description=\"Square thumbnail\"\n"
+    );
+}
+
+#[tokio::test]
+async fn applies_plan_and_reports_failures() {
+    let server = MockServer::start().await;
+    mount_existing(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/settings/presets"))
+        .and(body_json(
+            json!({ "name": "hero", "params": { "w": 1920, "format": "avif" } }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(preset(
+            "p4",
+            "hero",
+            None,
+            json!({ "w": 1920 }),
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api/v1/settings/presets/p2"))
+        .and(body_json(json!({
+            "description": "Square thumbnail",
+            "params": { "w": 200, "h": 200, "q": 80 }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(preset(
+            "p2",
+            "thumbnail",
+            Some("Square thumbnail"),
+            json!({ "w": 200, "h": 200, "q": 80 }),
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/settings/presets/legacy"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ImgSrcClient::from_configuration(make_config(&server));
+    let desired = PresetConfig::parse(DESIRED, ConfigFormat::Json).unwrap();
+    let options = PresetSyncOptions {
+        delete_missing: true,
+    };
+    let plan = client.presets().plan(&desired, &options).await.unwrap();
+    let report = client.presets().apply(plan).await;
+
+    assert_eq!(report.created().count(), 1);
+    assert_eq!(report.updated().count(), 1);
+    assert_eq!(report.deleted().count(), 0);
+    let failed: Vec<&str> = report.failed().map(|i| i.change.name()).collect();
+    assert_eq!(failed, ["legacy"]);
+    assert!(!report.is_success());
+}
+
+#[tokio::test]
+async fn matching_presets_need_no_changes() {
+    let server = MockServer::start().await;
+    mount_existing(&server).await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    // An unset description leaves the remote one alone.
+    let desired = PresetConfig::parse(
+        r#"{ "presets": { "banner": { "params": { "fit": "cover", "h": 400, "w": 1200 } } } }"#,
+        ConfigFormat::Json,
+    )
+    .unwrap();
+    let config = make_config(&server);
+    let plan = plan_presets(&config, &desired, &PresetSyncOptions::default())
+        .await
+        .unwrap();
+    assert!(!plan.has_changes());
+    assert_eq!(plan.to_string(), "");
+
+    let report = apply_presets(&config, plan).await;
+    assert_eq!(report.items.len(), 1);
+    assert!(report.is_success());
+}
+
+#[test]
+fn rejects_invalid_config() {
+    let err = PresetConfig::parse(
+        r#"{ "presets": { "thumb": { "params": { "width": 200 } } } }"#,
+        ConfigFormat::Json,
+    )
+    .unwrap_err();
+    match err {
+        PresetConfigError::InvalidParams { name, error } => {
+            assert_eq!(name, "thumb");
+            assert!(matches!(error, PresetParamsError::MisspelledKey { .. }));
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let err = PresetConfig::parse(
+        r#"{ "presets": { "thumb": { "parms": { "w": 200 } } } }"#,
+        ConfigFormat::Json,
+    )
+    .unwrap_err();
+    assert!(matches!(err, PresetConfigError::Parse(_)));
+}
+
+#[tokio::test]
+async fn loads_by_extension() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("presets.json");
+    std::fs::write(&file, DESIRED).unwrap();
+    let config = PresetConfig::load(&file).await.unwrap();
+    assert_eq!(config.presets.len(), 3);
+    assert_eq!(config.presets["banner"].params.fit, Some(Fit::Cover));
+
+    let err = PresetConfig::load(dir.path().join("presets.ini"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PresetConfigError::UnsupportedFormat(_)));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn parses_toml() {
+    let config = PresetConfig::parse(
+        r#"
+        [presets.thumbnail]
+        description = "Square thumbnail"
+        params = { w = 200, h = 200, q = 80 }
+        "#,
+        ConfigFormat::Toml,
+    )
+    .unwrap();
+    assert_eq!(
+        config.presets["thumbnail"].params.quality,
+        img_src::models::Quality::new(80)
+    );
+}
+
+#[cfg(not(feature = "toml"))]
+#[test]
+fn toml_requires_feature() {
+    let err = PresetConfig::parse("", ConfigFormat::Toml).unwrap_err();
+    assert!(matches!(err, PresetConfigError::UnsupportedFormat(_)));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn parses_yaml() {
+    let config = PresetConfig::parse(
+        "presets:\n  banner:\n    params:\n      w: 1200\n      fit: contain\n",
+        ConfigFormat::Yaml,
+    )
+    .unwrap();
+    assert_eq!(config.presets["banner"].params.fit, Some(Fit::Contain));
+}
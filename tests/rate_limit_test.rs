@@ -0,0 +1,141 @@
+use img_src::apis::configuration::{Configuration, Plan, RateLimiter};
+use img_src::apis::{presets_api, RateLimitInfo};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn usage_body(plan: &str) -> serde_json::Value {
+    json!({
+        "plan": plan,
+        "plan_name": "Plan",
+        "plan_status": "active",
+        "subscription_ends_at": null,
+        "plan_limits": {
+            "max_uploads_per_month": null,
+            "max_storage_bytes": null,
+            "max_bandwidth_per_month": null,
+            "max_api_requests_per_month": null,
+            "max_transformations_per_month": null
+        },
+        "total_images": 0,
+        "storage_used_bytes": 0,
+        "storage_used_mb": 0.0,
+        "storage_used_gb": 0.0,
+        "current_period": {
+            "period": "2024-01",
+            "period_start": 1704067200_i64,
+            "period_end": 1706745600_i64,
+            "uploads": 0,
+            "bandwidth_bytes": 0,
+            "api_requests": 0,
+            "transformations": 0
+        },
+        "credits": { "storage_bytes": 0, "api_requests": 0, "transformations": 0 }
+    })
+}
+
+#[test]
+fn plan_rates_and_parsing() {
+    assert_eq!(Plan::Free.requests_per_minute(), 100);
+    assert_eq!(Plan::Pro.requests_per_minute(), 500);
+    assert_eq!(Plan::from_plan_id("pro"), Some(Plan::Pro));
+    assert_eq!(Plan::from_plan_id(" Free "), Some(Plan::Free));
+    assert_eq!(Plan::from_plan_id("enterprise"), None);
+}
+
+#[test]
+fn limiter_for_plan() {
+    assert_eq!(RateLimiter::for_plan(Plan::Pro).requests_per_minute(), 500);
+    assert_eq!(RateLimiter::for_plan(Plan::Free).requests_per_minute(), 100);
+}
+
+#[test]
+fn limiter_update_adopts_server_limit() {
+    let limiter = RateLimiter::for_plan(Plan::Free);
+    limiter.update(&RateLimitInfo {
+        limit: 500,
+        remaining: 480,
+        reset: None,
+    });
+    assert_eq!(limiter.requests_per_minute(), 500);
+}
+
+#[tokio::test]
+async fn limiter_allows_burst_then_queues() {
+    // 6000/min refills one token every 10ms.
+    let limiter = RateLimiter::new(6000);
+    let start = Instant::now();
+    for _ in 0..6000 {
+        limiter.acquire().await;
+    }
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    let start = Instant::now();
+    limiter.acquire().await;
+    limiter.acquire().await;
+    assert!(start.elapsed() >= Duration::from_millis(10));
+}
+
+#[tokio::test]
+async fn limiter_waits_for_reset_when_exhausted() {
+    let limiter = RateLimiter::new(60_000);
+    limiter.update(&RateLimitInfo {
+        limit: 60_000,
+        remaining: 0,
+        reset: Some(1),
+    });
+    let acquired = tokio::time::timeout(Duration::from_millis(200), limiter.acquire()).await;
+    assert!(
+        acquired.is_err(),
+        "acquire should wait for the window reset"
+    );
+}
+
+#[tokio::test]
+async fn limiter_is_updated_from_response_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/settings/presets"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "presets": [], "total": 0 }))
+                .insert_header("x-ratelimit-limit", "500")
+                .insert_header("x-ratelimit-remaining", "499"),
+        )
+        .mount(&server)
+        .await;
+
+    let limiter = Arc::new(RateLimiter::for_plan(Plan::Free));
+    let mut config = Configuration::new();
+    config.base_path = server.uri();
+    config.bearer_access_token = Some("imgsrc_test".into());
+    config.rate_limiter = Some(limiter.clone());
+
+    presets_api::list_presets(&config).await.unwrap();
+    assert_eq!(limiter.requests_per_minute(), 500);
+}
+
+#[tokio::test]
+async fn limiter_detects_plan_from_usage() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/usage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(usage_body("pro")))
+        .mount(&server)
+        .await;
+
+    let mut config = Configuration::new();
+    config.base_path = server.uri();
+    config.bearer_access_token = Some("imgsrc_test".into());
+    let limiter = RateLimiter::detect(&config).await.unwrap();
+    assert_eq!(limiter.requests_per_minute(), 500);
+}
+
+#[test]
+fn limiter_from_unknown_plan_falls_back_to_free() {
+    let usage: img_src::models::UsageResponse =
+        serde_json::from_value(usage_body("enterprise")).unwrap();
+    assert_eq!(RateLimiter::from_usage(&usage).requests_per_minute(), 100);
+}
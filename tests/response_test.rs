@@ -0,0 +1,107 @@
+use img_src::apis::configuration::Configuration;
+use img_src::apis::{presets_api, RateLimitInfo};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in pairs {
+        map.insert(*name, HeaderValue::from_static(value));
+    }
+    map
+}
+
+#[test]
+fn rate_limit_info_from_headers() {
+    let info = RateLimitInfo::from_headers(&headers(&[
+        ("x-ratelimit-limit", "100"),
+        ("x-ratelimit-remaining", "42"),
+        ("x-ratelimit-reset", "1700000060"),
+    ]))
+    .unwrap();
+    assert_eq!(
+        info,
+        RateLimitInfo {
+            limit: 100,
+            remaining: 42,
+            reset: Some(1700000060),
+        }
+    );
+}
+
+#[test]
+fn rate_limit_info_without_reset() {
+    let info = RateLimitInfo::from_headers(&headers(&[
+        ("x-ratelimit-limit", "500"),
+        ("x-ratelimit-remaining", "0"),
+    ]))
+    .unwrap();
+    assert_eq!(info.remaining, 0);
+    assert_eq!(info.reset, None);
+}
+
+#[test]
+fn rate_limit_info_missing_or_malformed_is_none() {
+    assert!(RateLimitInfo::from_headers(&HeaderMap::new()).is_none());
+    assert!(RateLimitInfo::from_headers(&headers(&[
+        ("x-ratelimit-limit", "lots"),
+        ("x-ratelimit-remaining", "1"),
+    ]))
+    .is_none());
+}
+
+#[tokio::test]
+async fn with_response_returns_metadata() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/settings/presets"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "presets": [], "total": 0 }))
+                .insert_header("x-ratelimit-limit", "100")
+                .insert_header("x-ratelimit-remaining", "99")
+                .insert_header("x-ratelimit-reset", "1700000060")
+                .insert_header("x-request-id", "req_123"),
+        )
+        .mount(&server)
+        .await;
+
+    let mut config = Configuration::new();
+    config.base_path = server.uri();
+    config.bearer_access_token = Some("imgsrc_test".into());
+    let resp = presets_api::list_presets_with_response(&config)
+        .await
+        .unwrap();
+    assert_eq!(resp.status, 200);
+    assert_eq!(resp.request_id.as_deref(), Some("req_123"));
+    assert_eq!(resp.rate_limit.unwrap().remaining, 99);
+    assert_eq!(resp.headers["x-ratelimit-limit"], "100");
+    assert_eq!(resp.entity.total, 0);
+
+    let presets = presets_api::list_presets(&config).await.unwrap();
+    assert_eq!(presets, resp.into_entity());
+}
+
+#[tokio::test]
+async fn with_response_falls_back_to_cf_ray() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "presets": [], "total": 0 }))
+                .insert_header("cf-ray", "8a1b2c3d4e5f-ICN"),
+        )
+        .mount(&server)
+        .await;
+
+    let mut config = Configuration::new();
+    config.base_path = server.uri();
+    config.bearer_access_token = Some("imgsrc_test".into());
+    let resp = presets_api::list_presets_with_response(&config)
+        .await
+        .unwrap();
+    assert_eq!(resp.request_id.as_deref(), Some("8a1b2c3d4e5f-ICN"));
+    assert!(resp.rate_limit.is_none());
+}
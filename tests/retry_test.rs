@@ -0,0 +1,187 @@
+mod common;
+
+use common::{fast_retries, make_config_with, upload_body};
+use img_src::apis::configuration::{Configuration, RetryPolicy};
+use img_src::apis::{images_api, presets_api, Error};
+use serde_json::json;
+use std::io::Write;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn delete_preset_body() -> serde_json::Value {
+    json!({ "success": true, "message": "Preset deleted" })
+}
+
+#[test]
+fn retry_policy_backoff_doubles_and_caps() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(350),
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(350));
+    assert_eq!(policy.backoff(40), Duration::from_millis(350));
+}
+
+#[test]
+fn retry_policy_disabled_sends_once() {
+    assert_eq!(RetryPolicy::disabled().max_attempts, 1);
+    assert_eq!(Configuration::default().retry_policy.max_attempts, 3);
+}
+
+#[tokio::test]
+async fn retries_server_error_then_succeeds() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/settings/presets/thumb"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/settings/presets/thumb"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(delete_preset_body()))
+        .mount(&server)
+        .await;
+
+    let config = make_config_with(&server, fast_retries());
+    let resp = presets_api::delete_preset(&config, "thumb").await.unwrap();
+    assert!(resp.success);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let config = make_config_with(&server, fast_retries());
+    let err = presets_api::delete_preset(&config, "thumb")
+        .await
+        .unwrap_err();
+    match err {
+        Error::ResponseError(rc) => assert_eq!(rc.status, 500),
+        other => panic!("expected ResponseError, got {:?}", other),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn disabled_policy_does_not_retry() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let config = make_config_with(&server, RetryPolicy::disabled());
+    assert!(presets_api::delete_preset(&config, "thumb").await.is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn post_server_error_is_not_retried_by_default() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let config = make_config_with(&server, fast_retries());
+    assert!(presets_api::create_preset(&config, None).await.is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn upload_retries_rate_limit_and_reopens_file() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(upload_body()))
+        .mount(&server)
+        .await;
+
+    let mut file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+    file.write_all(b"retry-test-image-bytes").unwrap();
+    file.flush().unwrap();
+
+    let config = make_config_with(&server, fast_retries());
+    let upload = images_api::upload_image(
+        &config,
+        Some(file.path().to_path_buf()),
+        Some("photos"),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(upload.id, "abc123");
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    for request in requests {
+        let body = String::from_utf8_lossy(&request.body);
+        assert!(body.contains("retry-test-image-bytes"));
+    }
+}
+
+#[tokio::test]
+async fn retry_after_longer_than_max_backoff_is_not_waited_for() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "3600"))
+        .mount(&server)
+        .await;
+
+    let config = make_config_with(&server, fast_retries());
+    assert!(presets_api::delete_preset(&config, "thumb").await.is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn retry_after_accepts_http_date() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/settings/presets/past"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT"),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/settings/presets/future"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "Fri, 01 Jan 2100 00:00:00 GMT"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    // A date in the past is retried at once; one far ahead is not waited for.
+    let config = make_config_with(&server, fast_retries());
+    let err = presets_api::delete_preset(&config, "past")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ResponseError(ref r) if r.status == 404));
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    assert!(presets_api::delete_preset(&config, "future").await.is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
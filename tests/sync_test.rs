@@ -0,0 +1,490 @@
+mod common;
+
+use common::{make_config, upload_body_for, write};
+use img_src::dedup::{image_id_for_hash, sha256_bytes};
+use img_src::sync::{
+    sync_dir, Manifest, SyncAction, SyncOptions, SyncOutcome, UploadReason, MANIFEST_VERSION,
+};
+use img_src::ImgSrcClient;
+use serde_json::json;
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn options() -> SyncOptions {
+    SyncOptions {
+        target_prefix: Some("assets".into()),
+        verify_remote: false,
+        username: Some("alice".into()),
+        ..SyncOptions::default()
+    }
+}
+
+/// Answers uploads containing `contents` with the matching hash and `paths`.
+async fn mount_upload(server: &MockServer, contents: &str, remote_path: &str) {
+    let hash = sha256_bytes(contents.as_bytes());
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .and(body_string_contains(contents))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(upload_body_for(&hash, &[remote_path])),
+        )
+        .mount(server)
+        .await;
+}
+
+async fn posts(server: &MockServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.method == wiremock::http::Method::POST)
+        .count()
+}
+
+#[tokio::test]
+async fn uploads_new_files_then_is_incremental() {
+    let server = MockServer::start().await;
+    mount_upload(&server, "logo-v1", "assets/logo.png").await;
+    mount_upload(&server, "hero-v1", "assets/banners/hero.jpg").await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "logo.png", "logo-v1");
+    write(dir.path(), "banners/hero.jpg", "hero-v1");
+    write(dir.path(), "README.md", "not an image");
+
+    let config = make_config(&server);
+    let mut manifest = Manifest::new();
+    let report = sync_dir(&config, dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert_eq!(report.uploaded().count(), 2);
+    assert!(report.is_success());
+    assert!(matches!(
+        report.items[0].action,
+        SyncAction::Upload {
+            reason: UploadReason::New,
+            ..
+        }
+    ));
+
+    let hero = &manifest.files["banners/hero.jpg"];
+    assert_eq!(hero.remote_path, "assets/banners/hero.jpg");
+    assert_eq!(hero.hash, sha256_bytes(b"hero-v1"));
+    assert_eq!(hero.image_id, image_id_for_hash(&hero.hash));
+    assert_eq!(manifest.version, MANIFEST_VERSION);
+
+    // Nothing changed: no further uploads.
+    let report = sync_dir(&config, dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert_eq!(report.unchanged().count(), 2);
+    assert_eq!(report.uploaded().count(), 0);
+    assert_eq!(posts(&server).await, 2);
+
+    // Only the changed file is uploaded again.
+    mount_upload(&server, "logo-v2", "assets/logo.png").await;
+    write(dir.path(), "logo.png", "logo-v2");
+    let report = sync_dir(&config, dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    let uploaded: Vec<_> = report.uploaded().collect();
+    assert_eq!(uploaded.len(), 1);
+    assert!(matches!(
+        uploaded[0].action,
+        SyncAction::Upload {
+            reason: UploadReason::Changed,
+            ..
+        }
+    ));
+    assert_eq!(manifest.files["logo.png"].hash, sha256_bytes(b"logo-v2"));
+    assert_eq!(posts(&server).await, 3);
+}
+
+#[tokio::test]
+async fn deletes_removed_files_only_when_asked() {
+    let server = MockServer::start().await;
+    mount_upload(&server, "old", "assets/old.png").await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/images/path/alice/assets%2Fold.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "message": "Path deleted",
+            "remaining_paths": [],
+            "image_deleted": true,
+            "deleted_at": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "old.png", "old");
+    let client = ImgSrcClient::from_configuration(make_config(&server));
+    let mut manifest = Manifest::new();
+    client
+        .images()
+        .sync_dir(dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+
+    std::fs::remove_file(dir.path().join("old.png")).unwrap();
+    let report = client
+        .images()
+        .sync_dir(dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert!(report.items.is_empty());
+    assert!(manifest.files.contains_key("old.png"));
+
+    let delete = SyncOptions {
+        delete_removed: true,
+        ..options()
+    };
+    let report = client
+        .images()
+        .sync_dir(dir.path(), &mut manifest, &delete)
+        .await
+        .unwrap();
+    assert_eq!(report.deleted().count(), 1);
+    assert!(manifest.files.is_empty());
+}
+
+#[tokio::test]
+async fn dry_run_plans_without_changes() {
+    let server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "a.png", "a");
+
+    let mut manifest = Manifest::new();
+    manifest.files.insert(
+        "gone.png".into(),
+        serde_json::from_value(json!({
+            "hash": "00",
+            "image_id": "00",
+            "remote_path": "assets/gone.png"
+        }))
+        .unwrap(),
+    );
+    let before = manifest.clone();
+    let dry_run = SyncOptions {
+        dry_run: true,
+        delete_removed: true,
+        ..options()
+    };
+    let report = sync_dir(&make_config(&server), dir.path(), &mut manifest, &dry_run)
+        .await
+        .unwrap();
+
+    assert_eq!(report.uploaded().count(), 1);
+    assert_eq!(report.deleted().count(), 1);
+    assert!(report
+        .items
+        .iter()
+        .all(|i| matches!(i.outcome, SyncOutcome::Planned)));
+    assert_eq!(manifest, before);
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn verify_remote_adopts_published_and_restores_missing() {
+    let server = MockServer::start().await;
+    let published = sha256_bytes(b"published");
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "images": [{
+                "id": image_id_for_hash(&published),
+                "original_filename": "a.png",
+                "visibility": "public",
+                "size": 9,
+                "uploaded_at": "2024-01-01T00:00:00Z",
+                "url": "/api/v1/images/x",
+                "paths": ["assets/a.png"]
+            }],
+            "folders": [],
+            "total": 1,
+            "limit": 100,
+            "offset": 0,
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+    mount_upload(&server, "restored", "assets/b.png").await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "a.png", "published");
+    write(dir.path(), "b.png", "restored");
+    let restored = sha256_bytes(b"restored");
+
+    let mut manifest = Manifest::new();
+    manifest.files.insert(
+        "b.png".into(),
+        serde_json::from_value(json!({
+            "hash": restored,
+            "image_id": image_id_for_hash(&restored),
+            "remote_path": "assets/b.png"
+        }))
+        .unwrap(),
+    );
+    let verify = SyncOptions {
+        verify_remote: true,
+        ..options()
+    };
+    let report = sync_dir(&make_config(&server), dir.path(), &mut manifest, &verify)
+        .await
+        .unwrap();
+
+    assert!(matches!(report.items[0].action, SyncAction::Adopt { .. }));
+    assert!(matches!(
+        report.items[1].action,
+        SyncAction::Upload {
+            reason: UploadReason::MissingRemotely,
+            ..
+        }
+    ));
+    assert_eq!(manifest.files["a.png"].hash, published);
+    assert_eq!(posts(&server).await, 1);
+}
+
+/// Answers the deletion of `remote_path` for user `alice`, exactly once.
+async fn expect_path_delete(server: &MockServer, remote_path: &str) {
+    Mock::given(method("DELETE"))
+        .and(path(format!(
+            "/api/v1/images/path/alice/{}",
+            remote_path.replace('/', "%2F")
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "message": "Path deleted",
+            "remaining_paths": [],
+            "image_deleted": true,
+            "deleted_at": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn methods(requests: &[wiremock::Request]) -> Vec<String> {
+    requests.iter().map(|r| r.method.to_string()).collect()
+}
+
+fn entry(contents: &str, remote_path: &str) -> img_src::sync::ManifestEntry {
+    let hash = sha256_bytes(contents.as_bytes());
+    serde_json::from_value(json!({
+        "hash": hash,
+        "image_id": image_id_for_hash(&hash),
+        "remote_path": remote_path
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn changed_content_frees_its_path_after_conflict() {
+    let server = MockServer::start().await;
+    expect_path_delete(&server, "assets/logo.png").await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .respond_with(ResponseTemplate::new(409))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    mount_upload(&server, "logo-v2", "assets/logo.png").await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "logo.png", "logo-v2");
+    let mut manifest = Manifest::new();
+    manifest
+        .files
+        .insert("logo.png".into(), entry("logo-v1", "assets/logo.png"));
+
+    let report = sync_dir(&make_config(&server), dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert!(report.is_success());
+    assert!(matches!(
+        &report.items[0].action,
+        SyncAction::Upload {
+            reason: UploadReason::Changed,
+            replaces: Some(stale),
+            ..
+        } if stale == "assets/logo.png"
+    ));
+    assert_eq!(
+        methods(&server.received_requests().await.unwrap()),
+        ["POST", "DELETE", "POST"]
+    );
+    assert_eq!(manifest.files["logo.png"].hash, sha256_bytes(b"logo-v2"));
+}
+
+#[tokio::test]
+async fn moved_target_removes_the_old_path() {
+    let server = MockServer::start().await;
+    expect_path_delete(&server, "static/logo.png").await;
+    mount_upload(&server, "logo", "assets/logo.png").await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "logo.png", "logo");
+    let mut manifest = Manifest::new();
+    // Published before `target_prefix` changed from `static` to `assets`.
+    manifest
+        .files
+        .insert("logo.png".into(), entry("logo", "static/logo.png"));
+
+    let report = sync_dir(&make_config(&server), dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert!(report.is_success());
+    assert_eq!(
+        report.items[0].action.removed_path(),
+        Some("static/logo.png")
+    );
+    assert_eq!(
+        methods(&server.received_requests().await.unwrap()),
+        ["POST", "DELETE"]
+    );
+    assert_eq!(manifest.files["logo.png"].remote_path, "assets/logo.png");
+}
+
+#[tokio::test]
+async fn failed_upload_keeps_the_old_path() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "logo.png", "logo");
+    let mut manifest = Manifest::new();
+    manifest
+        .files
+        .insert("logo.png".into(), entry("logo", "static/logo.png"));
+
+    let report = sync_dir(&make_config(&server), dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert!(matches!(
+        report.items[0].outcome,
+        SyncOutcome::UploadFailed(_)
+    ));
+    assert_eq!(
+        methods(&server.received_requests().await.unwrap()),
+        ["POST"]
+    );
+    assert_eq!(manifest.files["logo.png"].remote_path, "static/logo.png");
+}
+
+#[tokio::test]
+async fn records_the_path_returned_for_sanitized_names() {
+    let server = MockServer::start().await;
+    mount_upload(&server, "cat", "alice/assets/my-cat.png").await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "my cat.png", "cat");
+    let mut manifest = Manifest::new();
+    let config = make_config(&server);
+    sync_dir(&config, dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert_eq!(
+        manifest.files["my cat.png"].remote_path,
+        "assets/my-cat.png"
+    );
+
+    let report = sync_dir(&config, dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert_eq!(report.unchanged().count(), 1);
+    assert_eq!(posts(&server).await, 1);
+}
+
+#[tokio::test]
+async fn verify_remote_ignores_copies_in_other_folders() {
+    let server = MockServer::start().await;
+    let hash = sha256_bytes(b"cat");
+    Mock::given(method("GET"))
+        .and(path("/api/v1/images"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "images": [{
+                "id": image_id_for_hash(&hash),
+                "original_filename": "cat.png",
+                "visibility": "public",
+                "size": 3,
+                "uploaded_at": "2024-01-01T00:00:00Z",
+                "url": "/api/v1/images/x",
+                "paths": ["alice/old/assets/cat.png"]
+            }],
+            "folders": [],
+            "total": 1,
+            "limit": 100,
+            "offset": 0,
+            "has_more": false
+        })))
+        .mount(&server)
+        .await;
+    mount_upload(&server, "cat", "assets/cat.png").await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "cat.png", "cat");
+    let verify = SyncOptions {
+        verify_remote: true,
+        ..options()
+    };
+    let mut manifest = Manifest::new();
+    let report = sync_dir(&make_config(&server), dir.path(), &mut manifest, &verify)
+        .await
+        .unwrap();
+    assert!(matches!(
+        report.items[0].action,
+        SyncAction::Upload {
+            reason: UploadReason::New,
+            ..
+        }
+    ));
+    assert_eq!(posts(&server).await, 1);
+}
+
+#[tokio::test]
+async fn failed_upload_leaves_manifest_untouched() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "a.png", "a");
+    let mut manifest = Manifest::new();
+    let report = sync_dir(&make_config(&server), dir.path(), &mut manifest, &options())
+        .await
+        .unwrap();
+    assert!(!report.is_success());
+    assert!(matches!(
+        report.failed().next().unwrap().outcome,
+        SyncOutcome::UploadFailed(_)
+    ));
+    assert!(manifest.files.is_empty());
+}
+
+#[tokio::test]
+async fn manifest_load_missing_and_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("manifest.json");
+    let mut manifest = Manifest::load(&file).await.unwrap();
+    assert_eq!(manifest, Manifest::new());
+
+    manifest.files.insert(
+        "a.png".into(),
+        serde_json::from_value(json!({
+            "hash": "ab",
+            "image_id": "ab",
+            "remote_path": "a.png",
+            "paths": ["a.png"]
+        }))
+        .unwrap(),
+    );
+    manifest.save(&file).await.unwrap();
+    assert_eq!(Manifest::load(&file).await.unwrap(), manifest);
+}
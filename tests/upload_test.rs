@@ -0,0 +1,117 @@
+mod common;
+
+use common::{fast_retries, make_config_with, upload_body};
+use img_src::apis::{images_api, Error};
+use img_src::models::Visibility;
+use img_src::ImgSrcClient;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_upload(server: &MockServer, first_status: Option<u16>) {
+    if let Some(status) = first_status {
+        Mock::given(method("POST"))
+            .and(path("/api/v1/images"))
+            .respond_with(ResponseTemplate::new(status).insert_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .mount(server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/api/v1/images"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(upload_body()))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn upload_bytes_sends_file_name_and_content_type() {
+    let server = MockServer::start().await;
+    mount_upload(&server, None).await;
+
+    let config = make_config_with(&server, fast_retries());
+    let upload = images_api::upload_image_bytes(
+        &config,
+        b"in-memory-image".to_vec(),
+        "thumb.png",
+        "image/png",
+        Some("thumbs"),
+        Some(Visibility::Private),
+    )
+    .await
+    .unwrap();
+    assert_eq!(upload.id, "abc123");
+
+    let requests = server.received_requests().await.unwrap();
+    let body = String::from_utf8_lossy(&requests[0].body);
+    assert!(body.contains(r#"name="file"; filename="thumb.png""#));
+    assert!(body.contains("Content-Type: image/png"));
+    assert!(body.contains("in-memory-image"));
+    assert!(body.contains("thumbs"));
+    assert!(body.contains("private"));
+}
+
+#[tokio::test]
+async fn upload_bytes_is_resent_on_retry() {
+    let server = MockServer::start().await;
+    mount_upload(&server, Some(429)).await;
+
+    let config = make_config_with(&server, fast_retries());
+    let data = bytes::Bytes::from_static(b"retried-bytes");
+    images_api::upload_image_bytes(&config, data, "a.jpg", "image/jpeg", None, None)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    for request in requests {
+        assert!(String::from_utf8_lossy(&request.body).contains("retried-bytes"));
+    }
+}
+
+#[tokio::test]
+async fn upload_bytes_rejects_invalid_content_type() {
+    let server = MockServer::start().await;
+    let config = make_config_with(&server, fast_retries());
+    let result =
+        images_api::upload_image_bytes(&config, vec![1, 2, 3], "a.jpg", "not a mime", None, None)
+            .await;
+    assert!(matches!(result, Err(Error::Reqwest(_))));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn upload_reader_streams_body() {
+    let server = MockServer::start().await;
+    mount_upload(&server, None).await;
+
+    let client = ImgSrcClient::from_configuration(make_config_with(&server, fast_retries()));
+    let reader = std::io::Cursor::new(b"streamed-image".to_vec());
+    let upload = client
+        .images()
+        .upload_reader(reader, "stream.webp", "image/webp", None, None)
+        .await
+        .unwrap();
+    assert_eq!(upload.id, "abc123");
+
+    let requests = server.received_requests().await.unwrap();
+    let body = String::from_utf8_lossy(&requests[0].body);
+    assert!(body.contains(r#"filename="stream.webp""#));
+    assert!(body.contains("Content-Type: image/webp"));
+    assert!(body.contains("streamed-image"));
+}
+
+#[tokio::test]
+async fn upload_reader_is_not_retried() {
+    let server = MockServer::start().await;
+    mount_upload(&server, Some(503)).await;
+
+    let config = make_config_with(&server, fast_retries());
+    let reader = std::io::Cursor::new(b"once".to_vec());
+    let result =
+        images_api::upload_image_reader(&config, reader, "a.png", "image/png", None, None).await;
+    match result {
+        Err(Error::ResponseError(content)) => assert_eq!(content.status, 503),
+        other => panic!("expected a 503 response error, got {other:?}"),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}